@@ -0,0 +1,730 @@
+use std::error::Error;
+use std::fmt;
+
+// escape : turn any text into a string literal for Rust, JSON or C, and back again
+// - every escape_* function returns the literal with its quotes
+// - every unescape_* function takes the literal with its quotes
+// - positions in errors are byte offsets into the literal
+
+// "a\tb" -> "\"a\\tb\""
+// non-ASCII chars are kept as they are, other control chars become \u{..}
+pub fn escape_rust(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\0' => literal.push_str("\\0"),
+            c if needs_unicode_escape(c) => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+// r"..." with as few '#' as it needs: one more than the longest run of '#' after a '"'
+// None when the text can't be a raw string, because raw strings can't hold a lone '\r'
+pub fn escape_rust_raw(text: &str) -> Option<String> {
+    if text.contains('\r') {
+        return None;
+    }
+    let hashes = if text.contains('"') {
+        longest_hash_run_after_quote(text) + 1
+    } else {
+        0
+    };
+    let hashes = "#".repeat(hashes);
+    Some(format!("r{hashes}\"{text}\"{hashes}"))
+}
+
+// what a code generator wants: a raw string when that is shorter, like for paths and regexes
+pub fn to_rust_literal(text: &str) -> String {
+    let escaped = escape_rust(text);
+    match escape_rust_raw(text) {
+        Some(raw) if raw.len() < escaped.len() && !text.chars().any(needs_unicode_escape) => raw,
+        _ => escaped,
+    }
+}
+
+// reads "..." and r#"..."# literals
+// - escapes : \n \r \t \\ \0 \' \" \x00-\x7F \u{0-10FFFF}
+// - a backslash at the end of a line skips the line break and the whitespace after it
+// - a lone '\r' isn't allowed in either kind, and a raw string can't hold a '"' followed by its own '#'s
+pub fn unescape_rust(literal: &str) -> Result<String, EscapeError> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let body = raw[hashes..]
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix(&closing))
+            .ok_or(EscapeError::MissingQuotes)?;
+        // 'r', the '#'s and the opening quote
+        let start = hashes + 2;
+        for (index, c) in body.char_indices() {
+            if c == '\r' || (c == '"' && body[index..].starts_with(&closing)) {
+                return Err(EscapeError::UnescapedChar {
+                    position: start + index,
+                    found: c,
+                });
+            }
+        }
+        return Ok(body.to_string());
+    }
+
+    let body = strip_quotes(literal)?;
+    let mut text = String::new();
+    let mut chars = body.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        // +1 for the opening quote
+        let position = index + 1;
+        if c == '"' || c == '\r' {
+            return Err(EscapeError::UnescapedChar { position, found: c });
+        }
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        let Some((_, escape)) = chars.next() else {
+            return Err(EscapeError::Unterminated);
+        };
+        match escape {
+            'n' => text.push('\n'),
+            'r' => text.push('\r'),
+            't' => text.push('\t'),
+            '0' => text.push('\0'),
+            '\\' | '\'' | '"' => text.push(escape),
+            '\n' => {
+                while chars.peek().is_some_and(|(_, next)| next.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            'x' => {
+                let value = read_hex(&mut chars, 2, position)?;
+                if value > 0x7F {
+                    return Err(EscapeError::InvalidCodepoint { position, value });
+                }
+                text.push(value as u8 as char);
+            }
+            'u' => {
+                if chars.next().map(|(_, open)| open) != Some('{') {
+                    return Err(EscapeError::InvalidHex { position });
+                }
+                let mut digits = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, '_')) => {}
+                        Some((_, digit)) if digit.is_ascii_hexdigit() && digits.len() < 6 => {
+                            digits.push(digit)
+                        }
+                        _ => return Err(EscapeError::InvalidHex { position }),
+                    }
+                }
+                let value = u32::from_str_radix(&digits, 16)
+                    .map_err(|_| EscapeError::InvalidHex { position })?;
+                text.push(
+                    char::from_u32(value)
+                        .ok_or(EscapeError::InvalidCodepoint { position, value })?,
+                );
+            }
+            other => {
+                return Err(EscapeError::UnknownEscape {
+                    position,
+                    escape: other,
+                })
+            }
+        }
+    }
+    Ok(text)
+}
+
+// "a\"b" -> "\"a\\\"b\"", non-ASCII chars are kept as they are
+pub fn escape_json(text: &str) -> String {
+    json(text, false)
+}
+
+// like escape_json, but only ASCII comes out: everything else becomes \uXXXX,
+// chars outside the Basic Multilingual Plane become a UTF-16 surrogate pair
+pub fn escape_json_ascii(text: &str) -> String {
+    json(text, true)
+}
+
+// reads a JSON string, including surrogate pairs like "😀"
+pub fn unescape_json(literal: &str) -> Result<String, EscapeError> {
+    let body = strip_quotes(literal)?;
+    let mut text = String::new();
+    let mut chars = body.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let position = index + 1;
+        if c == '"' || c < ' ' {
+            return Err(EscapeError::UnescapedChar { position, found: c });
+        }
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        let Some((_, escape)) = chars.next() else {
+            return Err(EscapeError::Unterminated);
+        };
+        match escape {
+            '"' | '\\' | '/' => text.push(escape),
+            'b' => text.push('\u{8}'),
+            'f' => text.push('\u{c}'),
+            'n' => text.push('\n'),
+            'r' => text.push('\r'),
+            't' => text.push('\t'),
+            'u' => {
+                let unit = read_hex(&mut chars, 4, position)?;
+                let value = match unit {
+                    0xD800..=0xDBFF => {
+                        // a high surrogate has to be followed by \u and a low surrogate
+                        let low = match (chars.next(), chars.next()) {
+                            (Some((_, '\\')), Some((_, 'u'))) => read_hex(&mut chars, 4, position)?,
+                            _ => {
+                                return Err(EscapeError::LoneSurrogate {
+                                    position,
+                                    value: unit,
+                                })
+                            }
+                        };
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err(EscapeError::LoneSurrogate {
+                                position,
+                                value: unit,
+                            });
+                        }
+                        0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                    }
+                    0xDC00..=0xDFFF => {
+                        return Err(EscapeError::LoneSurrogate {
+                            position,
+                            value: unit,
+                        })
+                    }
+                    _ => unit,
+                };
+                text.push(
+                    char::from_u32(value)
+                        .ok_or(EscapeError::InvalidCodepoint { position, value })?,
+                );
+            }
+            other => {
+                return Err(EscapeError::UnknownEscape {
+                    position,
+                    escape: other,
+                })
+            }
+        }
+    }
+    Ok(text)
+}
+
+// C strings are bytes, so non-ASCII text is written as the octal escapes of its UTF-8 bytes
+// octal always has 3 digits, so a digit after it can't be read as part of the escape
+// like "\xe4" + "5" would be
+pub fn escape_c(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for byte in text.bytes() {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            0x07 => literal.push_str("\\a"),
+            0x08 => literal.push_str("\\b"),
+            0x0B => literal.push_str("\\v"),
+            0x0C => literal.push_str("\\f"),
+            // "??" followed by some chars is a trigraph in older C
+            b'?' if literal.ends_with('?') => literal.push_str("\\?"),
+            b' '..=b'~' => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{byte:03o}")),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+// gives back bytes, use String::from_utf8 for text
+// - escapes : \a \b \f \n \r \t \v \\ \' \" \? octal \0 to \377 and hex \x..
+pub fn unescape_c(literal: &str) -> Result<Vec<u8>, EscapeError> {
+    let body = strip_quotes(literal)?;
+    let mut bytes = Vec::new();
+    let mut chars = body.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let position = index + 1;
+        if c == '"' {
+            return Err(EscapeError::UnescapedChar { position, found: c });
+        }
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        let Some((_, escape)) = chars.next() else {
+            return Err(EscapeError::Unterminated);
+        };
+        let byte = match escape {
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0C,
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => 0x0B,
+            '\\' | '\'' | '"' | '?' => escape as u8,
+            '0'..='7' => {
+                let mut value = escape.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    match chars.peek().and_then(|(_, digit)| digit.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                u8::try_from(value)
+                    .map_err(|_| EscapeError::InvalidCodepoint { position, value })?
+            }
+            'x' => {
+                let mut value = 0;
+                let mut digits = 0;
+                while let Some(digit) = chars.peek().and_then(|(_, digit)| digit.to_digit(16)) {
+                    value = value * 16 + digit;
+                    digits += 1;
+                    chars.next();
+                    if value > 0xFF {
+                        return Err(EscapeError::InvalidCodepoint { position, value });
+                    }
+                }
+                if digits == 0 {
+                    return Err(EscapeError::InvalidHex { position });
+                }
+                value as u8
+            }
+            other => {
+                return Err(EscapeError::UnknownEscape {
+                    position,
+                    escape: other,
+                })
+            }
+        };
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+// b"..." with printable ASCII as it is and every other byte as \x..
+pub fn byte_string_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("b\"");
+    for &byte in bytes {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            0 => literal.push_str("\\0"),
+            b' '..=b'~' => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn json(text: &str, ascii_only: bool) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\u{8}' => literal.push_str("\\b"),
+            '\u{c}' => literal.push_str("\\f"),
+            c if c < ' ' || c == '\u{7f}' || (ascii_only && !c.is_ascii()) => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    literal.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+// control chars and the invisible chars that would make a literal hard to read
+fn needs_unicode_escape(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{200B}'..='\u{200F}' | '\u{2028}'..='\u{202E}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}')
+}
+
+fn longest_hash_run_after_quote(text: &str) -> usize {
+    text.split('"')
+        .skip(1)
+        .map(|after_quote| after_quote.len() - after_quote.trim_start_matches('#').len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn strip_quotes(literal: &str) -> Result<&str, EscapeError> {
+    literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or(EscapeError::MissingQuotes)
+}
+
+fn read_hex(
+    chars: &mut impl Iterator<Item = (usize, char)>,
+    digits: usize,
+    position: usize,
+) -> Result<u32, EscapeError> {
+    let mut value = 0;
+    for _ in 0..digits {
+        let digit = chars
+            .next()
+            .and_then(|(_, digit)| digit.to_digit(16))
+            .ok_or(EscapeError::InvalidHex { position })?;
+        value = value * 16 + digit;
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EscapeError {
+    // the literal doesn't start and end with its quotes
+    MissingQuotes,
+    // the literal ends in the middle of an escape
+    Unterminated,
+    UnknownEscape { position: usize, escape: char },
+    InvalidHex { position: usize },
+    // the escape is well formed but the value is not a char (or byte)
+    InvalidCodepoint { position: usize, value: u32 },
+    // a JSON \uXXXX surrogate without its other half
+    LoneSurrogate { position: usize, value: u32 },
+    // a char that has to be escaped, like '"' inside the literal
+    UnescapedChar { position: usize, found: char },
+}
+
+impl fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EscapeError::MissingQuotes => write!(f, "the literal is not in quotes"),
+            EscapeError::Unterminated => write!(f, "the literal ends in the middle of an escape"),
+            EscapeError::UnknownEscape { position, escape } => {
+                write!(f, "unknown escape \\{escape} at byte {position}")
+            }
+            EscapeError::InvalidHex { position } => {
+                write!(f, "bad hex digits in the escape at byte {position}")
+            }
+            EscapeError::InvalidCodepoint { position, value } => {
+                write!(
+                    f,
+                    "the escape at byte {position} has value {value:#x}, which is out of range"
+                )
+            }
+            EscapeError::LoneSurrogate { position, value } => {
+                write!(
+                    f,
+                    "the escape at byte {position} is a surrogate {value:#06x} without its pair"
+                )
+            }
+            EscapeError::UnescapedChar { position, found } => {
+                write!(f, "{found:?} at byte {position} has to be escaped")
+            }
+        }
+    }
+}
+
+impl Error for EscapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXTS: [&str; 6] = [
+        "",
+        r##"I like to write "#"."##,
+        "서울\r\n\u{0}\u{1F600}",
+        "\\ and \u{202E}",
+        "tab\there, \"quotes\" and 'apostrophes'",
+        "\u{7}\u{8}\u{b}\u{c}\u{7f} ??= C:\\path",
+    ];
+
+    #[test]
+    fn rust_round_trips() {
+        for text in TEXTS {
+            assert_eq!(
+                unescape_rust(&escape_rust(text)),
+                Ok(text.to_string()),
+                "{text:?}"
+            );
+            assert_eq!(
+                unescape_rust(&to_rust_literal(text)),
+                Ok(text.to_string()),
+                "{text:?}"
+            );
+            if let Some(raw) = escape_rust_raw(text) {
+                assert_eq!(unescape_rust(&raw), Ok(text.to_string()), "{text:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        for text in TEXTS {
+            assert_eq!(
+                unescape_json(&escape_json(text)),
+                Ok(text.to_string()),
+                "{text:?}"
+            );
+            let ascii = escape_json_ascii(text);
+            assert!(ascii.is_ascii());
+            assert_eq!(unescape_json(&ascii), Ok(text.to_string()), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn c_round_trips() {
+        for text in TEXTS {
+            let literal = escape_c(text);
+            assert!(literal.is_ascii());
+            assert_eq!(
+                unescape_c(&literal),
+                Ok(text.as_bytes().to_vec()),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn raw_strings_use_as_few_hashes_as_they_can() {
+        assert_eq!(
+            escape_rust_raw(r"C:\Users"),
+            Some(r#"r"C:\Users""#.to_string())
+        );
+        assert_eq!(escape_rust_raw("a#b"), Some("r\"a#b\"".to_string()));
+        assert_eq!(
+            escape_rust_raw(r#"say "hi""#),
+            Some(r##"r#"say "hi""#"##.to_string())
+        );
+        assert_eq!(
+            escape_rust_raw(r##"a"#b"##),
+            Some(r###"r##"a"#b"##"###.to_string())
+        );
+        assert_eq!(
+            escape_rust_raw(r###"a"##b"###),
+            Some(r####"r###"a"##b"###"####.to_string())
+        );
+        // a run of '#' that doesn't follow a quote can't end the literal
+        assert_eq!(
+            escape_rust_raw(r#"### "x""#),
+            Some(r####"r#"### "x""#"####.to_string())
+        );
+        assert_eq!(escape_rust_raw("line\r\n"), None);
+    }
+
+    #[test]
+    fn picks_the_shorter_rust_literal() {
+        assert_eq!(to_rust_literal(r"C:\Users\Shou"), r#"r"C:\Users\Shou""#);
+        assert_eq!(to_rust_literal("plain"), "\"plain\"");
+        // invisible chars are always escaped, even when a raw string would be shorter
+        assert_eq!(
+            to_rust_literal("\\\\\\\u{200B}"),
+            "\"\\\\\\\\\\\\\\u{200b}\""
+        );
+    }
+
+    #[test]
+    fn reads_rust_escapes() {
+        assert_eq!(
+            unescape_rust("\"caf\\u{e9} \\x41\\\n    B\""),
+            Ok("café AB".to_string())
+        );
+        assert_eq!(
+            unescape_rust(r#""\u{1F6_00}""#),
+            Ok("\u{1F600}".to_string())
+        );
+        assert_eq!(unescape_rust(r##"r#"a"b"#"##), Ok("a\"b".to_string()));
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(escape_json_ascii("\u{1F600}"), r#""\ud83d\ude00""#);
+        assert_eq!(escape_json_ascii("\u{10FFFF}"), r#""\udbff\udfff""#);
+        assert_eq!(
+            unescape_json(r#""\ud83d\ude00 \u00e9""#),
+            Ok("\u{1F600} é".to_string())
+        );
+        assert_eq!(
+            unescape_json(r#""\uD83D\uDE00""#),
+            Ok("\u{1F600}".to_string())
+        );
+        assert_eq!(
+            unescape_json(r#""\ud83d""#),
+            Err(EscapeError::LoneSurrogate {
+                position: 1,
+                value: 0xd83d
+            })
+        );
+        assert_eq!(
+            unescape_json(r#""x\ud83d\u0041""#),
+            Err(EscapeError::LoneSurrogate {
+                position: 2,
+                value: 0xd83d
+            })
+        );
+        assert_eq!(
+            unescape_json(r#""ab\ude00""#),
+            Err(EscapeError::LoneSurrogate {
+                position: 3,
+                value: 0xde00
+            })
+        );
+    }
+
+    #[test]
+    fn errors_say_where() {
+        assert_eq!(unescape_json("no quotes"), Err(EscapeError::MissingQuotes));
+        assert_eq!(unescape_json("\""), Err(EscapeError::MissingQuotes));
+        assert_eq!(unescape_rust(r#"r#"a""#), Err(EscapeError::MissingQuotes));
+        assert_eq!(
+            unescape_rust(r#"r"a"b""#),
+            Err(EscapeError::UnescapedChar {
+                position: 3,
+                found: '"'
+            })
+        );
+        assert_eq!(
+            unescape_rust(r##"r#"a"#b"#"##),
+            Err(EscapeError::UnescapedChar {
+                position: 4,
+                found: '"'
+            })
+        );
+        assert_eq!(
+            unescape_rust("r\"a\rb\""),
+            Err(EscapeError::UnescapedChar {
+                position: 3,
+                found: '\r'
+            })
+        );
+        assert_eq!(
+            unescape_rust("\"a\rb\""),
+            Err(EscapeError::UnescapedChar {
+                position: 2,
+                found: '\r'
+            })
+        );
+        assert_eq!(
+            unescape_rust(r###"r##"a"#b"##"###),
+            Ok(r##"a"#b"##.to_string())
+        );
+        assert_eq!(unescape_rust(r##"r#"a"b"#"##), Ok(r#"a"b"#.to_string()));
+        assert_eq!(
+            unescape_rust("r\"a\r\nb\""),
+            Err(EscapeError::UnescapedChar {
+                position: 3,
+                found: '\r'
+            })
+        );
+        assert_eq!(
+            unescape_json(r#""ab\q""#),
+            Err(EscapeError::UnknownEscape {
+                position: 3,
+                escape: 'q'
+            })
+        );
+        assert_eq!(
+            unescape_rust(r#""\q""#),
+            Err(EscapeError::UnknownEscape {
+                position: 1,
+                escape: 'q'
+            })
+        );
+        assert_eq!(
+            unescape_c(r#""\z""#),
+            Err(EscapeError::UnknownEscape {
+                position: 1,
+                escape: 'z'
+            })
+        );
+        assert_eq!(
+            unescape_json(r#""\u12g4""#),
+            Err(EscapeError::InvalidHex { position: 1 })
+        );
+        assert_eq!(
+            unescape_rust(r#""é\u{}""#),
+            Err(EscapeError::InvalidHex { position: 3 })
+        );
+        assert_eq!(
+            unescape_c(r#""\x""#),
+            Err(EscapeError::InvalidHex { position: 1 })
+        );
+        assert_eq!(
+            unescape_rust(r#""\u{110000}""#),
+            Err(EscapeError::InvalidCodepoint {
+                position: 1,
+                value: 0x110000
+            })
+        );
+        assert_eq!(
+            unescape_rust(r#""\x80""#),
+            Err(EscapeError::InvalidCodepoint {
+                position: 1,
+                value: 0x80
+            })
+        );
+        assert_eq!(
+            unescape_c(r#""\400""#),
+            Err(EscapeError::InvalidCodepoint {
+                position: 1,
+                value: 0o400
+            })
+        );
+        assert_eq!(
+            unescape_json(r#""a"b""#),
+            Err(EscapeError::UnescapedChar {
+                position: 2,
+                found: '"'
+            })
+        );
+        assert_eq!(
+            unescape_json("\"a\nb\""),
+            Err(EscapeError::UnescapedChar {
+                position: 2,
+                found: '\n'
+            })
+        );
+        assert_eq!(unescape_rust(r#""\"#), Err(EscapeError::MissingQuotes));
+        assert_eq!(unescape_rust(r#""a\""#), Err(EscapeError::Unterminated));
+    }
+
+    #[test]
+    fn byte_strings() {
+        assert_eq!(
+            byte_string_literal(b"I like \"#\"\n\0"),
+            r##"b"I like \"#\"\n\0""##
+        );
+        assert_eq!(byte_string_literal("서".as_bytes()), r#"b"\xec\x84\x9c""#);
+        assert_eq!(byte_string_literal(&[0xff, b'\\']), r#"b"\xff\\""#);
+    }
+
+    #[test]
+    fn c_escapes() {
+        assert_eq!(
+            escape_c("Zürich?? \"5\"\t\u{7}"),
+            r#""Z\303\274rich?\? \"5\"\t\a""#
+        );
+        assert_eq!(unescape_c(r#""\x41\101\0""#), Ok(vec![65, 65, 0]));
+    }
+}
@@ -0,0 +1,425 @@
+use std::fmt;
+
+use crate::layout::{self, Align};
+use crate::table::Table;
+
+// inspect : what each char of a string really is, for finding encoding problems
+// run `cargo run -- <text>` to see the report for any text
+// there is no Unicode database here, so category and script come from the std char methods
+// and the ranges of the common blocks: good for debugging, not for deciding anything
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    UppercaseLetter,
+    LowercaseLetter,
+    OtherLetter,
+    NonspacingMark,
+    SpacingMark,
+    DecimalNumber,
+    OtherNumber,
+    ConnectorPunctuation,
+    DashPunctuation,
+    OpenPunctuation,
+    ClosePunctuation,
+    InitialPunctuation,
+    FinalPunctuation,
+    OtherPunctuation,
+    MathSymbol,
+    CurrencySymbol,
+    ModifierSymbol,
+    OtherSymbol,
+    SpaceSeparator,
+    LineSeparator,
+    ParagraphSeparator,
+    Control,
+    Format,
+    PrivateUse,
+}
+
+impl Category {
+    pub fn of(c: char) -> Self {
+        match c {
+            '\u{2028}' => Category::LineSeparator,
+            '\u{2029}' => Category::ParagraphSeparator,
+            '\u{00AD}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}' => Category::Format,
+            '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{10FFFF}' => Category::PrivateUse,
+            c if c.is_control() => Category::Control,
+            c if c.is_whitespace() => Category::SpaceSeparator,
+            c if c.is_uppercase() => Category::UppercaseLetter,
+            c if c.is_lowercase() => Category::LowercaseLetter,
+            // marks are checked before letters, since vowel signs are alphabetic too
+            c if layout::is_spacing_mark(c) => Category::SpacingMark,
+            // Hangul vowels and final consonants take no width of their own, but they are letters
+            '\u{1160}'..='\u{11FF}' => Category::OtherLetter,
+            c if layout::char_width(c) == 0 => Category::NonspacingMark,
+            c if c.is_alphabetic() => Category::OtherLetter,
+            c if c.is_numeric() => {
+                if is_decimal_digit(c) {
+                    Category::DecimalNumber
+                } else {
+                    Category::OtherNumber
+                }
+            }
+            c => punctuation_or_symbol(c),
+        }
+    }
+
+    // the two letter name Unicode uses, like "Lu"
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Category::UppercaseLetter => "Lu",
+            Category::LowercaseLetter => "Ll",
+            Category::OtherLetter => "Lo",
+            Category::NonspacingMark => "Mn",
+            Category::SpacingMark => "Mc",
+            Category::DecimalNumber => "Nd",
+            Category::OtherNumber => "No",
+            Category::ConnectorPunctuation => "Pc",
+            Category::DashPunctuation => "Pd",
+            Category::OpenPunctuation => "Ps",
+            Category::ClosePunctuation => "Pe",
+            Category::InitialPunctuation => "Pi",
+            Category::FinalPunctuation => "Pf",
+            Category::OtherPunctuation => "Po",
+            Category::MathSymbol => "Sm",
+            Category::CurrencySymbol => "Sc",
+            Category::ModifierSymbol => "Sk",
+            Category::OtherSymbol => "So",
+            Category::SpaceSeparator => "Zs",
+            Category::LineSeparator => "Zl",
+            Category::ParagraphSeparator => "Zp",
+            Category::Control => "Cc",
+            Category::Format => "Cf",
+            Category::PrivateUse => "Co",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abbreviation())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Georgian,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Han,
+    // combining marks that take the script of the char before them
+    Inherited,
+    // digits, punctuation, symbols and emoji that every script uses
+    Common,
+    Unknown,
+}
+
+impl Script {
+    pub fn of(c: char) -> Self {
+        match c {
+            '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{200C}'..='\u{200D}' => Script::Inherited,
+            'a'..='z'
+            | 'A'..='Z'
+            | 'ª'
+            | 'º'
+            | '\u{00C0}'..='\u{024F}'
+            | '\u{1E00}'..='\u{1EFF}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}' => {
+                if c == '×' || c == '÷' {
+                    Script::Common
+                } else {
+                    Script::Latin
+                }
+            }
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Script::Greek,
+            '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+            '\u{0530}'..='\u{058F}' => Script::Armenian,
+            '\u{0590}'..='\u{05FF}' => Script::Hebrew,
+            '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' => Script::Arabic,
+            '\u{0900}'..='\u{097F}' => Script::Devanagari,
+            '\u{0E00}'..='\u{0E7F}' => Script::Thai,
+            '\u{10A0}'..='\u{10FF}' => Script::Georgian,
+            '\u{1100}'..='\u{11FF}'
+            | '\u{3130}'..='\u{318F}'
+            | '\u{A960}'..='\u{A97F}'
+            | '\u{AC00}'..='\u{D7FF}' => Script::Hangul,
+            '\u{3041}'..='\u{3096}' | '\u{309D}'..='\u{309F}' => Script::Hiragana,
+            '\u{30A1}'..='\u{30FA}'
+            | '\u{30FD}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{FF66}'..='\u{FF9D}' => Script::Katakana,
+            '\u{2E80}'..='\u{2FDF}'
+            | '々'
+            | '〇'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{3FFFF}' => Script::Han,
+            c if c.is_alphabetic() => Script::Unknown,
+            _ => Script::Common,
+        }
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+// everything about one char of the inspected text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharInfo {
+    pub character: char,
+    // where the char starts in the text, in bytes and in UTF-16 code units (like JavaScript)
+    pub byte_offset: usize,
+    pub utf16_offset: usize,
+    pub utf8: Vec<u8>,
+    pub utf16: Vec<u16>,
+    pub category: Category,
+    pub script: Script,
+}
+
+impl CharInfo {
+    // U+D589
+    pub fn codepoint(&self) -> String {
+        format!("U+{:04X}", self.character as u32)
+    }
+
+    // control and invisible chars are shown as their escape, so the report stays readable
+    pub fn printable(&self) -> String {
+        match self.category {
+            Category::Control
+            | Category::Format
+            | Category::LineSeparator
+            | Category::ParagraphSeparator => self.character.escape_unicode().to_string(),
+            // a combining mark shown on a dotted circle, like fonts do
+            Category::NonspacingMark | Category::SpacingMark => {
+                format!("\u{25CC}{}", self.character)
+            }
+            _ => self.character.to_string(),
+        }
+    }
+}
+
+pub fn inspect(text: &str) -> Vec<CharInfo> {
+    let mut utf16_offset = 0;
+    text.char_indices()
+        .map(|(byte_offset, character)| {
+            let mut buffer = [0; 2];
+            let utf16 = character.encode_utf16(&mut buffer).to_vec();
+            let start = utf16_offset;
+            utf16_offset += utf16.len();
+            CharInfo {
+                character,
+                byte_offset,
+                utf16_offset: start,
+                utf8: character.to_string().into_bytes(),
+                utf16,
+                category: Category::of(character),
+                script: Script::of(character),
+            }
+        })
+        .collect()
+}
+
+// one line per char, as a table
+pub fn report(text: &str) -> String {
+    let mut table = Table::new(&[
+        "Byte",
+        "UTF-16",
+        "Char",
+        "Codepoint",
+        "UTF-8",
+        "UTF-16 units",
+        "Category",
+        "Script",
+    ]);
+    for column in [0, 1] {
+        table
+            .set_align(column, Align::Right)
+            .expect("the table has these columns");
+    }
+    for info in inspect(text) {
+        let utf8 = info
+            .utf8
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<String>>();
+        let utf16 = info
+            .utf16
+            .iter()
+            .map(|unit| format!("{unit:04X}"))
+            .collect::<Vec<String>>();
+        table
+            .add_row(&[
+                info.byte_offset.to_string(),
+                info.utf16_offset.to_string(),
+                info.printable(),
+                info.codepoint(),
+                utf8.join(" "),
+                utf16.join(" "),
+                info.category.to_string(),
+                info.script.to_string(),
+            ])
+            .expect("the row has one cell per column");
+    }
+    format!(
+        "{text:?}: {} bytes, {} UTF-16 units, {} chars\n{table}",
+        text.len(),
+        text.encode_utf16().count(),
+        text.chars().count()
+    )
+}
+
+fn is_decimal_digit(c: char) -> bool {
+    // each of these blocks has its digits 0 to 9 in order
+    const ZEROS: [char; 6] = [
+        '0', '\u{0660}', '\u{06F0}', '\u{0966}', '\u{0E50}', '\u{FF10}',
+    ];
+    ZEROS
+        .iter()
+        .any(|&zero| (zero..=char::from_u32(zero as u32 + 9).unwrap_or(zero)).contains(&c))
+        || ('\u{1D7CE}'..='\u{1D7FF}').contains(&c)
+}
+
+fn punctuation_or_symbol(c: char) -> Category {
+    match c {
+        '_' | '‿' | '⁀' | '＿' => Category::ConnectorPunctuation,
+        '-' | '\u{2010}'..='\u{2015}' | '〜' | '－' => Category::DashPunctuation,
+        '(' | '[' | '{' | '‚' | '„' | '（' | '［' | '｛' => Category::OpenPunctuation,
+        ')' | ']' | '}' | '）' | '］' | '｝' => Category::ClosePunctuation,
+        // 〈 《 「 『 【 〔 open and the char after each of them closes
+        '\u{3008}'..='\u{3011}' | '\u{3014}'..='\u{301B}' => {
+            if (c as u32).is_multiple_of(2) {
+                Category::OpenPunctuation
+            } else {
+                Category::ClosePunctuation
+            }
+        }
+        '«' | '‘' | '‛' | '“' | '‟' | '‹' => Category::InitialPunctuation,
+        '»' | '’' | '”' | '›' => Category::FinalPunctuation,
+        '+'
+        | '<'
+        | '='
+        | '>'
+        | '|'
+        | '~'
+        | '¬'
+        | '±'
+        | '×'
+        | '÷'
+        | '\u{2190}'..='\u{21FF}'
+        | '\u{2200}'..='\u{22FF}' => Category::MathSymbol,
+        '$' | '¢' | '£' | '¤' | '¥' | '\u{20A0}'..='\u{20CF}' | '￥' | '￦' => {
+            Category::CurrencySymbol
+        }
+        '^'
+        | '`'
+        | '¨'
+        | '¯'
+        | '´'
+        | '¸'
+        | '\u{02C2}'..='\u{02C5}'
+        | '\u{02D2}'..='\u{02DF}'
+        | '\u{309B}'
+        | '\u{309C}'
+        | '\u{1F3FB}'..='\u{1F3FF}' => Category::ModifierSymbol,
+        '!'..='/'
+        | ':'..='@'
+        | '\\'
+        | '¡'
+        | '§'
+        | '¶'
+        | '·'
+        | '¿'
+        | '\u{2016}'..='\u{205E}'
+        | '\u{3001}'..='\u{3003}'
+        | '\u{FF01}'..='\u{FF0F}'
+        | '\u{FF1A}'..='\u{FF20}' => Category::OtherPunctuation,
+        _ => Category::OtherSymbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories(text: &str) -> Vec<&'static str> {
+        text.chars()
+            .map(|c| Category::of(c).abbreviation())
+            .collect()
+    }
+
+    #[test]
+    fn devanagari_vowel_signs() {
+        // क + ि (Mc) , क + ु (Mn) , क + ् (virama, Mn) + ष
+        assert_eq!(categories("कि"), ["Lo", "Mc"]);
+        assert_eq!(categories("कु"), ["Lo", "Mn"]);
+        assert_eq!(categories("क्ष"), ["Lo", "Mn", "Lo"]);
+        for c in '\u{093E}'..='\u{0940}' {
+            assert_eq!(
+                Category::of(c),
+                Category::SpacingMark,
+                "{}",
+                c.escape_unicode()
+            );
+        }
+        for c in '\u{0949}'..='\u{094C}' {
+            assert_eq!(
+                Category::of(c),
+                Category::SpacingMark,
+                "{}",
+                c.escape_unicode()
+            );
+        }
+        assert_eq!(Category::of('\u{0941}'), Category::NonspacingMark);
+        assert_eq!(Category::of('\u{0903}'), Category::SpacingMark);
+        assert_eq!(Category::of('\u{0902}'), Category::NonspacingMark);
+    }
+
+    #[test]
+    fn conjoining_jamo_are_letters() {
+        // ᄒ + ᅡ + ᆫ, written out as jamo instead of 한
+        assert_eq!(categories("\u{1112}\u{1161}\u{11AB}"), ["Lo", "Lo", "Lo"]);
+        assert_eq!(Category::of('\u{1160}'), Category::OtherLetter);
+        assert_eq!(Category::of('\u{11FF}'), Category::OtherLetter);
+        assert_eq!(Category::of('한'), Category::OtherLetter);
+    }
+
+    #[test]
+    fn combining_marks_and_letters() {
+        assert_eq!(categories("e\u{301}"), ["Ll", "Mn"]);
+        assert_eq!(categories("か\u{3099}"), ["Lo", "Mn"]);
+        assert_eq!(categories("A1 ٣\u{200B}"), ["Lu", "Nd", "Zs", "Nd", "Cf"]);
+    }
+
+    #[test]
+    fn marks_are_printed_on_a_dotted_circle() {
+        let info = inspect("कि");
+        assert_eq!(info[1].printable(), "\u{25CC}\u{093F}");
+        assert_eq!(info[1].codepoint(), "U+093F");
+        assert_eq!(info[1].byte_offset, 3);
+        assert_eq!(inspect("\u{1112}\u{1161}")[1].printable(), "\u{1161}");
+    }
+}
@@ -0,0 +1,339 @@
+// `format!("{:^11}")` counts chars, but a terminal shows CJK chars two columns wide
+// and combining marks on top of the char before them,
+// so everything here counts display columns instead
+
+pub const ELLIPSIS: char = '…';
+
+// chars that take no column of their own: nonspacing and enclosing marks (Mn and Me),
+// zero width spaces and joiners, variation selectors
+// and the Hangul vowels and final consonants that join the char before them
+// - spacing marks (Mc) like Devanagari 'ा' take a column, see `is_spacing_mark`
+const ZERO_WIDTH: [(u32, u32); 37] = [
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0900, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x2028, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xE0100, 0xE01EF),
+];
+
+// East Asian wide and fullwidth chars, and emoji, take two columns
+const DOUBLE_WIDTH: [(u32, u32); 21] = [
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F300, 0x1F64F),
+    (0x1F900, 0x1F9FF),
+    (0x20000, 0x3FFFD),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+// how many terminal columns a char takes: 0, 1 or 2
+pub fn char_width(c: char) -> usize {
+    let code = c as u32;
+    let in_table = |table: &[(u32, u32)]| {
+        table
+            .iter()
+            .any(|&(first, last)| (first..=last).contains(&code))
+    };
+
+    if c.is_control() || in_table(&ZERO_WIDTH) {
+        0
+    } else if in_table(&DOUBLE_WIDTH) {
+        2
+    } else {
+        1
+    }
+}
+
+// Devanagari vowel signs that take up room next to the consonant, like 'ा' in "का",
+// and the Hangul tone marks
+// - the other Devanagari signs are in ZERO_WIDTH: they sit above or below, and are Mn
+pub fn is_spacing_mark(c: char) -> bool {
+    matches!(c,
+        '\u{0903}'
+        | '\u{093B}'
+        | '\u{093E}'..='\u{0940}'
+        | '\u{0949}'..='\u{094C}'
+        | '\u{094E}'..='\u{094F}'
+        | '\u{302E}'..='\u{302F}')
+}
+
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+pub fn pad_left(text: &str, width: usize, fill: char) -> String {
+    align(text, width, Align::Right, fill)
+}
+
+pub fn pad_right(text: &str, width: usize, fill: char) -> String {
+    align(text, width, Align::Left, fill)
+}
+
+pub fn center(text: &str, width: usize, fill: char) -> String {
+    align(text, width, Align::Center, fill)
+}
+
+// pads `text` with `fill` up to `width` columns, like `{:fill<width}` and friends
+// - text that is already wider is left alone
+// - if a wide fill char like 'ㅎ' can't fill the gap exactly, the last column is a space
+// - like `format!`, centering puts the extra column on the right
+pub fn align(text: &str, width: usize, align: Align, fill: char) -> String {
+    let gap = width.saturating_sub(display_width(text));
+    let (left, right) = match align {
+        Align::Left => (0, gap),
+        Align::Right => (gap, 0),
+        Align::Center => (gap / 2, gap - gap / 2),
+    };
+    format!(
+        "{}{text}{}",
+        fill_columns(left, fill),
+        fill_columns(right, fill)
+    )
+}
+
+// cuts `text` down to `width` columns, ending with '…' when something was cut off
+// combining marks stay with the char they belong to
+pub fn truncate(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let room = width - char_width(ELLIPSIS);
+    let mut used = 0;
+    let mut truncated = String::new();
+    for c in text.chars() {
+        let c_width = char_width(c);
+        if used + c_width > room {
+            break;
+        }
+        used += c_width;
+        truncated.push(c);
+    }
+    // a wide char that didn't fit leaves a gap before the ellipsis
+    truncated.push_str(&" ".repeat(room - used));
+    truncated.push(ELLIPSIS);
+    truncated
+}
+
+// breaks `text` into lines of at most `width` columns
+// - breaks between words where it can, and inside words that are wider than a line
+// - CJK text has no spaces, so it can break between any two wide chars
+// - '\n' always starts a new line
+// - a char wider than `width`, like '東' with a width of 1, gets a line of its own instead of being lost
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let word_width = display_width(word);
+            let line_width = display_width(&line);
+            let joined_width = if line.is_empty() {
+                word_width
+            } else {
+                line_width + 1 + word_width
+            };
+            if joined_width <= width {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+            // the word goes on the next lines, split wherever it has to be
+            if !line.is_empty() && word_width <= width {
+                lines.push(std::mem::take(&mut line));
+                line.push_str(word);
+                continue;
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            for c in word.chars() {
+                if !line.trim_end().is_empty() && display_width(&line) + char_width(c) > width {
+                    lines.push(line.trim_end().to_string());
+                    line.clear();
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// fits every cell into its column: too long cells are truncated, short ones are padded
+// missing widths or alignments mean "as wide as the cell" and Align::Left
+pub fn join_columns(cells: &[&str], widths: &[usize], aligns: &[Align], separator: &str) -> String {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let width = widths
+                .get(i)
+                .copied()
+                .unwrap_or_else(|| display_width(cell));
+            let alignment = aligns.get(i).copied().unwrap_or(Align::Left);
+            align(&truncate(cell, width), width, alignment, ' ')
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn fill_columns(columns: usize, fill: char) -> String {
+    let fill_width = char_width(fill);
+    if fill_width == 0 {
+        return " ".repeat(columns);
+    }
+    let mut filled = fill.to_string().repeat(columns / fill_width);
+    filled.push_str(&" ".repeat(columns % fill_width));
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("東京"), 4);
+        assert_eq!(display_width("서울"), 4);
+        assert_eq!(display_width("ｱｲ"), 2);
+        assert_eq!(display_width("Zu\u{308}rich"), 6);
+        assert_eq!(display_width("😀👍🏽"), 6);
+        assert_eq!(display_width("👨\u{200D}👩"), 4);
+        assert_eq!(display_width("\u{1112}\u{1161}\u{11AB}"), 2);
+        assert_eq!(display_width("a\tb"), 2);
+        // क + ि takes two columns, क + ु only one
+        assert_eq!(display_width("कि"), 2);
+        assert_eq!(display_width("कु"), 1);
+        assert_eq!(char_width('\u{0903}'), 1);
+        assert_eq!(char_width('\u{093D}'), 1);
+        assert_eq!(char_width('\u{05C3}'), 1);
+    }
+
+    #[test]
+    fn spacing_marks_and_zero_width_never_overlap() {
+        for code in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(code) {
+                assert!(
+                    !(is_spacing_mark(c) && char_width(c) == 0),
+                    "{}",
+                    c.escape_unicode()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn aligns_by_columns() {
+        assert_eq!(align("東京", 6, Align::Left, '.'), "東京..");
+        assert_eq!(align("東京", 6, Align::Right, ' '), "  東京");
+        assert_eq!(align("ab", 5, Align::Center, '*'), "*ab**");
+        assert_eq!(align("e\u{301}", 3, Align::Center, '-'), "-e\u{301}-");
+        assert_eq!(align("toolong", 3, Align::Left, ' '), "toolong");
+        // a wide fill that can't fill the gap exactly ends with a space
+        assert_eq!(pad_left("a", 4, 'ㅎ'), "ㅎ a");
+        assert_eq!(pad_right("a", 3, '.'), "a..");
+        assert_eq!(center("a", 4, ' '), " a  ");
+    }
+
+    #[test]
+    fn truncates_with_an_ellipsis() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello world", 5), "hell…");
+        assert_eq!(truncate("東京タワー", 5), "東京…");
+        assert_eq!(truncate("東京タワー", 4), "東 …");
+        assert_eq!(truncate("Zu\u{308}rich!", 4), "Zu\u{308}r…");
+        assert_eq!(truncate("😀😀", 3), "😀…");
+        assert_eq!(truncate("abc", 0), "");
+        assert_eq!(truncate("abc", 1), "…");
+    }
+
+    #[test]
+    fn wraps_words_and_wide_chars() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("a verylongword", 6), ["a very", "longwo", "rd"]);
+        assert_eq!(wrap("東京は日本の首都", 6), ["東京は", "日本の", "首都"]);
+        assert_eq!(wrap("one\ntwo three", 5), ["one", "two", "three"]);
+        assert_eq!(wrap("Zu\u{308}rich Bern", 6), ["Zu\u{308}rich", "Bern"]);
+        assert_eq!(wrap("😀😀😀", 4), ["😀😀", "😀"]);
+        assert_eq!(wrap("", 4), [""]);
+    }
+
+    #[test]
+    fn wrap_keeps_chars_wider_than_a_line() {
+        assert_eq!(wrap("東京", 1), ["東", "京"]);
+        assert_eq!(wrap("ab", 0), ["a", "b"]);
+        assert_eq!(wrap("a 東", 2), ["a", "東"]);
+    }
+
+    #[test]
+    fn joins_columns() {
+        let row = join_columns(
+            &["서울", "Seoul", "9668465"],
+            &[6, 7, 5],
+            &[Align::Left, Align::Center, Align::Right],
+            "|",
+        );
+        assert_eq!(row, "서울  | Seoul |9668…");
+        assert_eq!(join_columns(&["a", "b"], &[3], &[], " "), "a   b");
+    }
+}
@@ -1,7 +1,30 @@
+use std::fmt::Write;
+
+use layout::Align;
+use radix::Integer;
+use rope::Rope;
+use table::{Border, Table};
+use text_stats::TextStats;
+
+mod escape;
+mod inspect;
+mod layout;
+mod radix;
+mod rope;
+mod table;
+mod text_stats;
+
 const NUMBER_OF_MONTHS: u32 = 12;
 static SEASONS: [&str; 4] = ["Spring", "Summer", "Fall", "Winter"];
 
 fn main() {
+    // `cargo run -- 행H居い` only prints what each char of the text is
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if !args.is_empty() {
+        println!("{}", inspect::report(&args.join(" ")));
+        return;
+    }
+
     // reference : a memory-safe pointer
     // - A reference means you borrow the value, but you don’t own it.
     // - In Rust, reference has a `&` in front of them
@@ -89,11 +112,50 @@ fn main() {
         get_length(&my_string);
     }
 
+    // a Rope keeps count while it grows, so nothing is counted twice
+    let mut my_rope = Rope::new();
+    for _ in 0..50 {
+        my_rope.push_str("Here are some more ");
+        my_rope.push_str("words ");
+    }
+    println!("It's {} words long.", my_rope.word_count());
+    for i in 0..10_000 {
+        write!(my_rope, "line {i}: 서울 ").unwrap();
+    }
+    my_rope.push('!');
+    println!(
+        "{} bytes, {} chars, {} words in {} chunks",
+        my_rope.len(),
+        my_rope.char_count(),
+        my_rope.word_count(),
+        my_rope.chunk_count()
+    );
+    println!("{:?}", my_rope.slice(1_250..1_272));
+    println!(
+        "{:?} {:?}",
+        my_rope.char_at(my_rope.char_count() - 1),
+        my_rope.char_at(my_rope.char_count())
+    );
+    println!(
+        "'서' appears {} times",
+        my_rope.chars().filter(|&c| c == '서').count()
+    );
+    let joined = ["Here", " are", "", " some", " words"]
+        .into_iter()
+        .collect::<Rope>();
+    println!(
+        "{joined}: {} words, empty: {}",
+        joined.word_count(),
+        Rope::from("").is_empty()
+    );
+
     // print!()
     print!("\\tThis is Shou\n\tNice to meet you.\n");
-    println!("This is Shou
+    println!(
+        "This is Shou
 Nice to meet you
-hahaha");
+hahaha"
+    );
 
     // ignore escape with r#""#
     println!(r#"This is \n "Shou""#);
@@ -110,6 +172,7 @@ hahaha");
     println!("{:X}", 'H' as u32);
     println!("{:X}", '居' as u32);
     println!("{:X}", 'い' as u32);
+    println!("{}", inspect::report("행H居い"));
 
     println!("\u{D589}, \u{48}, \u{5C45}, \u{3044}");
 
@@ -125,6 +188,40 @@ hahaha");
     println!("octal: {:o}", number);
     println!("hexadecimal: {:x}", number);
 
+    // any base from 2 to 36, any size, negative too
+    let big = Integer::parse("-123_456_789_012_345_678_901_234_567_890_123_456_789", 10).unwrap();
+    for base in [2, 3, 7, 16, 36] {
+        println!("base {base}: {}", big.to_string_radix(base).unwrap());
+    }
+    println!(
+        "{}",
+        Integer::from(0xF0F0_u128)
+            .to_string_grouped(2, 4, '_')
+            .unwrap()
+    );
+    println!("{}", big.to_string_grouped(10, 3, ',').unwrap());
+    println!(
+        "{:>12}|{:+}",
+        Integer::from(-42_i128),
+        Integer::from(7_i128)
+    );
+    println!(
+        "{:?}",
+        Integer::parse("ZZ", 36).ok().and_then(|zz| zz.to_i128())
+    );
+    for (text, base) in [("12", 1), ("-", 16), ("12g4", 16)] {
+        println!(
+            "{text} in base {base}: {}",
+            Integer::parse(text, base).unwrap_err()
+        );
+    }
+
+    println!(
+        "negative: {}, zero: {}",
+        big.is_negative(),
+        Integer::zero().is_zero()
+    );
+
     // print with index
     println!("{1}, {2}, {0}", "a", "b", "c");
     println!("{b}, {c}, {a}", a = "a", b = "b", c = "c");
@@ -142,6 +239,124 @@ hahaha");
     let a = "SEOUL";
     let b = "TOKYO";
     println!("{city1:-<15}{city2:->15}", city1 = a, city2 = b);
+
+    // format! counts chars, so Korean and Japanese text ends up too wide
+    println!("{: <10}|", "서울");
+    println!("{: <10}|", "Seoul");
+    // layout counts the columns the terminal really uses
+    println!("{}|", layout::pad_right("서울", 10, ' '));
+    println!("{}|", layout::pad_right("Seoul", 10, ' '));
+    println!("{}", layout::center(letter, 11, 'ㅎ'));
+    println!("{}", layout::pad_left("東京", 11, '-'));
+    println!(
+        "width of 'e\u{301}': {}, width of '東京': {}",
+        layout::display_width("e\u{301}"),
+        layout::display_width("東京")
+    );
+    println!("{}|", layout::truncate("大韓民国 서울특별시", 11));
+    let columns = [
+        ("서울", "SEOUL", "9,411,260"),
+        ("東京", "TOKYO", "14,047,594"),
+        ("Zu\u{308}rich", "ZÜRICH", "421,878"),
+    ];
+    for (local, latin, population) in columns {
+        let row = layout::join_columns(
+            &[local, latin, population],
+            &[8, 6, 12],
+            &[Align::Left, Align::Center, Align::Right],
+            " | ",
+        );
+        println!("{row}");
+    }
+
+    // the same data as a table, a Markdown table and CSV
+    let mut tallinn = Table::new(&["Year", "Population", "Note"]);
+    tallinn.set_align(1, Align::Right).unwrap();
+    tallinn.set_max_width(2, 20).unwrap();
+    for (year, population, note) in [
+        (1372, 3_250, "first count of the town, made by the Danish"),
+        (1851, 24_000, ""),
+        (2020, 437_619, "capital of Estonia"),
+    ] {
+        tallinn
+            .add_row(&[year.to_string(), population.to_string(), note.to_string()])
+            .unwrap();
+    }
+    println!("{tallinn}");
+    println!("{}", tallinn.to_markdown());
+    println!("{}", tallinn.to_csv());
+
+    let mut cities = Table::new(&["도시", "City", "Country, region"]);
+    cities.set_border(Border::Unicode);
+    cities.set_align(1, Align::Center).unwrap();
+    cities.add_row(&["서울", "Seoul", "Korea"]).unwrap();
+    cities.add_row(&["東京", "Tokyo", "Japan, Kantō"]).unwrap();
+    cities.add_row(&["Zu\u{308}rich"]).unwrap();
+    println!("{cities}");
+    cities.set_border(Border::None);
+    println!("{cities}");
+    println!("{}", cities.to_csv());
+    if let Err(error) = cities.add_row(&["a", "b", "c", "d"]) {
+        println!("{error}");
+    }
+    if let Err(error) = cities.set_max_width(5, 10) {
+        println!("{error}");
+    }
+    println!("{} cities, empty: {}", cities.len(), cities.is_empty());
+
+    // bytes, chars and what a reader sees as characters are three different counts
+    let samples = [
+        "Hello, world! How are you?",
+        "Zu\u{308}rich is in Switzerland.\nIt's not the capital...",
+        "東京は日本の首都です。大阪も大きい。",
+        "서울은 한국의 수도입니다. 정말?!",
+        "\u{1F1F0}\u{1F1F7} \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} \u{1F44B}\u{1F3FD}",
+    ];
+    for sample in samples {
+        println!("{sample:?}");
+        println!("  {}", TextStats::of(sample));
+    }
+    println!(
+        "{:?}",
+        text_stats::graphemes("e\u{301}\u{1100}\u{1161}\u{11A8}\r\n\u{1F1EF}\u{1F1F5}\u{1F1F0}")
+    );
+    println!(
+        "{:?}",
+        text_stats::words("東京タワー is Tokyo's tower, 서울타워 is Seoul's")
+    );
+    let text = "The cat sat. The dog sat too! A cat and a dog, the end.";
+    println!("{:?}", text_stats::most_frequent_words(text, 3));
+    println!("{} sentences", text_stats::sentence_count(text));
+
+    // the literals above, made by code instead of by hand
+    let quoted = r##"I like to write "#"."##;
+    println!("{}", escape::escape_rust(quoted));
+    println!("{:?}", escape::escape_rust_raw(quoted));
+    println!("{}", escape::to_rust_literal(r"C:\Users\Shou"));
+    println!(
+        "{}",
+        escape::to_rust_literal("This is Shou\n\tNice to meet you.\u{200B}")
+    );
+    println!(
+        "{}",
+        escape::byte_string_literal("I like to write \"#\". 서울\n".as_bytes())
+    );
+    println!("{}", escape::escape_json("서울 \"Seoul\"\n\u{1}"));
+    println!("{}", escape::escape_json_ascii("서울 \u{1F600}"));
+    println!("{}", escape::escape_c("Zürich?? \"5\"\t\u{7}"));
+
+    println!(
+        "{:?}",
+        escape::unescape_rust("\"caf\\u{e9} \\x41\\\n    B\"")
+    );
+    println!("{:?}", escape::unescape_json(r#""\ud83d\ude00 \u00e9""#));
+    println!("{:?}", escape::unescape_c(r#""\x41\101\0""#));
+    for bad in [r#""\q""#, r#""\ud83d""#, r#""a"b""#, "no quotes"] {
+        println!("JSON {bad} : {}", escape::unescape_json(bad).unwrap_err());
+    }
+    for bad in [r#""\u{110000}""#, r#""\x80""#, r#"r#"a""#] {
+        println!("Rust {bad} : {}", escape::unescape_rust(bad).unwrap_err());
+    }
 }
 
 fn add_hungary(country_name: &mut String) {
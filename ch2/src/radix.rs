@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// `{:b}`, `{:o}` and `{:x}` only know bases 2, 8 and 16 and stop at u128,
+// this works for any base from 2 to 36 and any size of number
+pub const MIN_RADIX: u32 = 2;
+pub const MAX_RADIX: u32 = 36;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+// Integer : a whole number of any size
+// - the magnitude is stored in base 2^32, least significant part first, with no zero parts at the end
+// - zero is an empty magnitude and is never negative
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Integer {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl Integer {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // "-ff", "1010_1010" or "ZZ" in base 36
+    // - a leading '+' or '-' is allowed, '_' between digits is ignored
+    // - letters can be upper or lower case
+    pub fn parse(text: &str, radix: u32) -> Result<Self, RadixError> {
+        check_radix(radix)?;
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || digits.starts_with('_') || digits.ends_with('_') {
+            return Err(RadixError::Empty);
+        }
+
+        let mut number = Self::zero();
+        for (position, c) in digits.char_indices() {
+            if c == '_' {
+                continue;
+            }
+            let digit = c.to_digit(radix).ok_or(RadixError::InvalidDigit {
+                digit: c,
+                position: position + text.len() - digits.len(),
+                radix,
+            })?;
+            number.multiply_add(radix, digit);
+        }
+        number.negative = negative && !number.is_zero();
+        Ok(number)
+    }
+
+    // lower case digits, with a '-' in front of negative numbers
+    pub fn to_string_radix(&self, radix: u32) -> Result<String, RadixError> {
+        check_radix(radix)?;
+        if self.is_zero() {
+            return Ok("0".to_string());
+        }
+
+        // divide by the biggest power of the radix that fits a u32,
+        // so each division gives many digits at once
+        let mut chunk = radix;
+        let mut chunk_digits = 1;
+        while let Some(bigger) = chunk.checked_mul(radix) {
+            chunk = bigger;
+            chunk_digits += 1;
+        }
+
+        let mut digits = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let mut remainder = divide(&mut rest, chunk);
+            for _ in 0..chunk_digits {
+                digits.push(DIGITS[(remainder % radix) as usize]);
+                remainder /= radix;
+                if rest.is_empty() && remainder == 0 {
+                    break;
+                }
+            }
+        }
+        if self.negative {
+            digits.push(b'-');
+        }
+        digits.reverse();
+        Ok(String::from_utf8(digits).expect("digits are ASCII"))
+    }
+
+    // digits in groups from the right, like "1111_0000" or "1,000,000"
+    pub fn to_string_grouped(
+        &self,
+        radix: u32,
+        group_size: usize,
+        separator: char,
+    ) -> Result<String, RadixError> {
+        let text = self.to_string_radix(radix)?;
+        Ok(group_digits(&text, group_size, separator))
+    }
+
+    // None when the number doesn't fit
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 4 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u128, |value, &part| (value << 32) | u128::from(part));
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    // self = self * factor + digit
+    fn multiply_add(&mut self, factor: u32, digit: u32) {
+        let mut carry = u64::from(digit);
+        for part in &mut self.magnitude {
+            let value = u64::from(*part) * u64::from(factor) + carry;
+            *part = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            self.magnitude.push(carry as u32);
+        }
+    }
+}
+
+impl From<u128> for Integer {
+    fn from(value: u128) -> Self {
+        let mut magnitude = Vec::new();
+        let mut rest = value;
+        while rest > 0 {
+            magnitude.push(rest as u32);
+            rest >>= 32;
+        }
+        Self {
+            negative: false,
+            magnitude,
+        }
+    }
+}
+
+impl From<i128> for Integer {
+    fn from(value: i128) -> Self {
+        let mut number = Self::from(value.unsigned_abs());
+        number.negative = value < 0;
+        number
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.to_string_radix(10).map_err(|_| fmt::Error)?;
+        f.pad_integral(!self.negative, "", text.trim_start_matches('-'))
+    }
+}
+
+impl FromStr for Integer {
+    type Err = RadixError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input, 10)
+    }
+}
+
+// "-1234567" -> "-1,234,567" with group_size 3 and ','
+pub fn group_digits(number: &str, group_size: usize, separator: char) -> String {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number),
+    };
+    if group_size == 0 {
+        return number.to_string();
+    }
+    let first = match digits.len() % group_size {
+        0 => group_size,
+        first => first,
+    };
+    let mut grouped = String::from(sign);
+    for (i, c) in digits.chars().enumerate() {
+        if i >= first && (i - first) % group_size == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+// divides the magnitude in place and gives back the remainder
+fn divide(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for part in magnitude.iter_mut().rev() {
+        let value = (remainder << 32) | u64::from(*part);
+        *part = (value / u64::from(divisor)) as u32;
+        remainder = value % u64::from(divisor);
+    }
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    remainder as u32
+}
+
+fn check_radix(radix: u32) -> Result<(), RadixError> {
+    if (MIN_RADIX..=MAX_RADIX).contains(&radix) {
+        Ok(())
+    } else {
+        Err(RadixError::InvalidRadix(radix))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RadixError {
+    InvalidRadix(u32),
+    // no digits, or only a sign, or '_' at the start or end
+    Empty,
+    // position is the byte offset in the parsed text
+    InvalidDigit {
+        digit: char,
+        position: usize,
+        radix: u32,
+    },
+}
+
+impl fmt::Display for RadixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RadixError::InvalidRadix(radix) => {
+                write!(
+                    f,
+                    "base {radix} is not supported, it must be {MIN_RADIX} to {MAX_RADIX}"
+                )
+            }
+            RadixError::Empty => write!(f, "there are no digits"),
+            RadixError::InvalidDigit {
+                digit,
+                position,
+                radix,
+            } => {
+                write!(
+                    f,
+                    "{digit:?} at byte {position} is not a digit in base {radix}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for RadixError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [i128; 9] = [
+        0,
+        1,
+        -1,
+        35,
+        -36,
+        255,
+        i64::MIN as i128,
+        i128::MAX,
+        i128::MIN,
+    ];
+
+    #[test]
+    fn round_trips_in_every_base() {
+        for value in VALUES {
+            let integer = Integer::from(value);
+            assert_eq!(integer.to_i128(), Some(value));
+            for base in MIN_RADIX..=MAX_RADIX {
+                let text = integer.to_string_radix(base).unwrap();
+                assert_eq!(
+                    Integer::parse(&text, base),
+                    Ok(integer.clone()),
+                    "{value} in base {base}"
+                );
+                assert_eq!(
+                    Integer::parse(&text.to_uppercase(), base),
+                    Ok(integer.clone())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_format() {
+        for value in VALUES {
+            let integer = Integer::from(value);
+            let sign = if value < 0 { "-" } else { "" };
+            assert_eq!(integer.to_string(), value.to_string());
+            assert_eq!(
+                integer.to_string_radix(2).unwrap(),
+                format!("{sign}{:b}", value.unsigned_abs())
+            );
+            assert_eq!(
+                integer.to_string_radix(8).unwrap(),
+                format!("{sign}{:o}", value.unsigned_abs())
+            );
+            assert_eq!(
+                integer.to_string_radix(16).unwrap(),
+                format!("{sign}{:x}", value.unsigned_abs())
+            );
+        }
+        assert_eq!(
+            Integer::from(i128::MIN).to_string_radix(16).unwrap(),
+            "-80000000000000000000000000000000"
+        );
+        assert_eq!(
+            Integer::from(u128::MAX).to_string_radix(36).unwrap(),
+            "f5lxx1zz5pnorynqglhzmsp33"
+        );
+    }
+
+    #[test]
+    fn round_trips_numbers_bigger_than_i128() {
+        let huge = Integer::parse(&"9".repeat(100), 10).unwrap();
+        assert_eq!(huge.to_i128(), None);
+        assert!(!huge.is_negative() && !huge.is_zero());
+        assert_eq!(huge.to_string(), "9".repeat(100));
+        for base in MIN_RADIX..=MAX_RADIX {
+            let grouped = huge.to_string_grouped(base, 4, '_').unwrap();
+            assert_eq!(
+                Integer::parse(&grouped, base),
+                Ok(huge.clone()),
+                "base {base}"
+            );
+            let negative = format!("-{grouped}");
+            assert!(Integer::parse(&negative, base).unwrap().is_negative());
+        }
+    }
+
+    #[test]
+    fn groups_digits_from_the_right() {
+        assert_eq!(
+            Integer::from(0xF0F0_u128)
+                .to_string_grouped(2, 4, '_')
+                .unwrap(),
+            "1111_0000_1111_0000"
+        );
+        assert_eq!(
+            Integer::from(-1_234_567_i128)
+                .to_string_grouped(10, 3, ',')
+                .unwrap(),
+            "-1,234,567"
+        );
+        assert_eq!(
+            Integer::from(123_i128)
+                .to_string_grouped(10, 3, ',')
+                .unwrap(),
+            "123"
+        );
+        assert_eq!(group_digits("12345", 0, ','), "12345");
+    }
+
+    #[test]
+    fn minus_zero_is_zero() {
+        let zero = "-0".parse::<Integer>().unwrap();
+        assert_eq!(zero, Integer::zero());
+        assert!(!zero.is_negative());
+        assert_eq!(zero.to_string(), "0");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(Integer::parse("12", 1), Err(RadixError::InvalidRadix(1)));
+        assert_eq!(Integer::parse("12", 37), Err(RadixError::InvalidRadix(37)));
+        assert_eq!(
+            Integer::zero().to_string_radix(1),
+            Err(RadixError::InvalidRadix(1))
+        );
+        assert_eq!(Integer::parse("-", 16), Err(RadixError::Empty));
+        assert_eq!(Integer::parse("", 10), Err(RadixError::Empty));
+        assert_eq!(Integer::parse("_1", 10), Err(RadixError::Empty));
+        assert_eq!(
+            Integer::parse("12g4", 16),
+            Err(RadixError::InvalidDigit {
+                digit: 'g',
+                position: 2,
+                radix: 16
+            })
+        );
+        assert_eq!(
+            Integer::parse("-102", 2),
+            Err(RadixError::InvalidDigit {
+                digit: '2',
+                position: 3,
+                radix: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+use std::ops::Range;
+
+// chunks are filled up to this many bytes before a new one is started
+pub const CHUNK_SIZE: usize = 4 * 1024;
+
+#[derive(Debug, Clone, Default)]
+struct Chunk {
+    text: String,
+    // how many chars come before this chunk, to find a char without walking every chunk
+    char_start: usize,
+    char_count: usize,
+}
+
+// Rope : a string built piece by piece, for text that grows very large
+// - a String copies everything it has each time it runs out of capacity,
+//   a Rope only ever adds to its last chunk, so nothing already written is copied again
+// - bytes, chars and words are counted as text comes in, so asking for them is free
+// - a word is what `split_whitespace` would give, and can run over from one push to the next
+#[derive(Debug, Clone, Default)]
+pub struct Rope {
+    chunks: Vec<Chunk>,
+    bytes: usize,
+    chars: usize,
+    words: usize,
+    // whether the last char was part of a word, so the next push knows if it continues it
+    in_word: bool,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        let mut char_count = 0;
+        for c in text.chars() {
+            char_count += 1;
+            let is_word_char = !c.is_whitespace();
+            if is_word_char && !self.in_word {
+                self.words += 1;
+            }
+            self.in_word = is_word_char;
+        }
+
+        let needs_new_chunk = self
+            .chunks
+            .last()
+            .is_none_or(|last| last.text.len() + text.len() > CHUNK_SIZE && !last.text.is_empty());
+        if needs_new_chunk {
+            self.chunks.push(Chunk {
+                text: String::with_capacity(CHUNK_SIZE.max(text.len())),
+                char_start: self.chars,
+                char_count: 0,
+            });
+        }
+        let last = self
+            .chunks
+            .last_mut()
+            .expect("there is always a chunk to write to");
+        last.text.push_str(text);
+        last.char_count += char_count;
+
+        self.bytes += text.len();
+        self.chars += char_count;
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    // in bytes, like String::len
+    pub fn len(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes == 0
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.chars
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks.iter().flat_map(|chunk| chunk.text.chars())
+    }
+
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        let chunk = self.chunk_with_char(index)?;
+        chunk.text.chars().nth(index - chunk.char_start)
+    }
+
+    // the chars in `range`, counted in chars like `chars().skip().take()` but
+    // only the chunks the range touches are read
+    // None when the range goes past the end
+    pub fn slice(&self, range: Range<usize>) -> Option<String> {
+        if range.start > range.end || range.end > self.chars {
+            return None;
+        }
+        let mut slice = String::new();
+        let first = self
+            .chunks
+            .partition_point(|chunk| chunk.char_start + chunk.char_count <= range.start);
+        for chunk in &self.chunks[first..] {
+            if chunk.char_start >= range.end {
+                break;
+            }
+            let start = range.start.saturating_sub(chunk.char_start);
+            let end = (range.end - chunk.char_start).min(chunk.char_count);
+            slice.push_str(
+                &chunk.text[byte_offset(&chunk.text, start)..byte_offset(&chunk.text, end)],
+            );
+        }
+        Some(slice)
+    }
+
+    fn chunk_with_char(&self, index: usize) -> Option<&Chunk> {
+        if index >= self.chars {
+            return None;
+        }
+        let position = self
+            .chunks
+            .partition_point(|chunk| chunk.char_start + chunk.char_count <= index);
+        self.chunks.get(position)
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in &self.chunks {
+            f.write_str(&chunk.text)?;
+        }
+        Ok(())
+    }
+}
+
+// so `write!(rope, "...")` works like it does for a String
+impl fmt::Write for Rope {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.push_str(text);
+        Ok(())
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let mut rope = Self::new();
+        rope.push_str(text);
+        rope
+    }
+}
+
+impl<'a> Extend<&'a str> for Rope {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, pieces: I) {
+        for piece in pieces {
+            self.push_str(piece);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Rope {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(pieces: I) -> Self {
+        let mut rope = Self::new();
+        rope.extend(pieces);
+        rope
+    }
+}
+
+// the byte offset of the `chars`th char, or the end of the text
+fn byte_offset(text: &str, chars: usize) -> usize {
+    text.char_indices()
+        .nth(chars)
+        .map_or(text.len(), |(offset, _)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    fn big_rope() -> Rope {
+        let mut rope = Rope::new();
+        for i in 0..2_000 {
+            write!(rope, "line {i}: 서울 ").unwrap();
+        }
+        rope
+    }
+
+    #[test]
+    fn counts_match_the_whole_text() {
+        let rope = big_rope();
+        let whole = rope.to_string();
+        assert!(rope.chunk_count() > 1);
+        assert_eq!(rope.len(), whole.len());
+        assert_eq!(rope.char_count(), whole.chars().count());
+        assert_eq!(rope.word_count(), whole.split_whitespace().count());
+        assert_eq!(rope.chars().collect::<String>(), whole);
+        assert_eq!(rope.slice(0..rope.char_count()), Some(whole));
+    }
+
+    #[test]
+    fn a_word_split_across_pushes_is_one_word() {
+        let mut rope = Rope::new();
+        rope.push_str("Here are some mo");
+        rope.push_str("re words");
+        assert_eq!(rope.word_count(), 5);
+        rope.push(' ');
+        rope.push_str(" ");
+        rope.push('x');
+        assert_eq!(rope.word_count(), 6);
+        let joined = ["Here", " are", "", " some", " words"]
+            .into_iter()
+            .collect::<Rope>();
+        assert_eq!(joined.word_count(), 4);
+        assert_eq!(joined.to_string(), "Here are some words");
+    }
+
+    #[test]
+    fn slices_across_chunk_boundaries() {
+        let rope = big_rope();
+        let whole = rope.to_string().chars().collect::<Vec<char>>();
+        let first_chunk_chars = rope.chunks[0].char_count;
+        for (start, end) in [
+            (first_chunk_chars - 5, first_chunk_chars + 5),
+            (first_chunk_chars, first_chunk_chars + 1),
+            (first_chunk_chars - 1, first_chunk_chars),
+            (0, rope.char_count()),
+            (10, 10),
+        ] {
+            let expected = whole[start..end].iter().collect::<String>();
+            assert_eq!(rope.slice(start..end), Some(expected), "{start}..{end}");
+        }
+        assert_eq!(rope.slice(0..rope.char_count() + 1), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let backwards = rope.slice(5..4);
+        assert_eq!(backwards, None);
+    }
+
+    #[test]
+    fn char_at_the_end() {
+        let rope = big_rope();
+        let last = rope.char_count() - 1;
+        assert_eq!(rope.char_at(last), Some(' '));
+        assert_eq!(rope.char_at(last - 1), Some('울'));
+        assert_eq!(rope.char_at(rope.char_count()), None);
+        assert_eq!(rope.char_at(0), Some('l'));
+        assert_eq!(Rope::new().char_at(0), None);
+    }
+
+    #[test]
+    fn big_pushes_get_their_own_chunk() {
+        let mut rope = Rope::from("small");
+        let big = "x".repeat(CHUNK_SIZE * 2);
+        rope.push_str(&big);
+        rope.push_str("tail");
+        assert_eq!(rope.chunk_count(), 3);
+        assert_eq!(rope.slice(3..7), Some("llxx".to_string()));
+        assert_eq!(rope.word_count(), 1);
+        assert!(Rope::from("").is_empty());
+    }
+}
@@ -0,0 +1,499 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::layout::{self, Align};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    // columns separated by two spaces, a line of '-' under the headers
+    None,
+    // +----+----+ and |
+    Ascii,
+    // ┌────┬────┐ and │
+    Unicode,
+}
+
+// the chars a border is drawn with
+struct BorderChars {
+    horizontal: char,
+    vertical: char,
+    // left, middle and right corners of the top, header and bottom lines
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+const ASCII_BORDER: BorderChars = BorderChars {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+', '+', '+'],
+    middle: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+};
+
+const UNICODE_BORDER: BorderChars = BorderChars {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+// the narrowest a column can be made: a wide char like '東' needs two columns
+pub const MIN_COLUMN_WIDTH: usize = 2;
+
+// Table : rows of text that can be shown as a text table, Markdown or CSV
+// - columns are as wide as their widest cell, counted in display columns, so CJK text lines up
+// - a column with a max width wraps its cells onto more lines
+// - the chapters don't share code, so this only lives here: the reports in ch6, ch7 and ch9
+//   stay as they are, and main shows ch6's population by year as a table instead
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    max_widths: Vec<Option<usize>>,
+    rows: Vec<Vec<String>>,
+    border: Border,
+}
+
+impl Table {
+    pub fn new<S: ToString>(headers: &[S]) -> Self {
+        Self {
+            headers: headers.iter().map(ToString::to_string).collect(),
+            aligns: vec![Align::Left; headers.len()],
+            max_widths: vec![None; headers.len()],
+            rows: Vec::new(),
+            border: Border::Ascii,
+        }
+    }
+
+    pub fn set_align(&mut self, column: usize, align: Align) -> Result<(), TableError> {
+        let count = self.headers.len();
+        let slot = self
+            .aligns
+            .get_mut(column)
+            .ok_or(TableError::NoSuchColumn { column, count })?;
+        *slot = align;
+        Ok(())
+    }
+
+    // at least MIN_COLUMN_WIDTH, anything narrower couldn't show a wide char
+    pub fn set_max_width(&mut self, column: usize, width: usize) -> Result<(), TableError> {
+        if width < MIN_COLUMN_WIDTH {
+            return Err(TableError::WidthTooSmall { column, width });
+        }
+        let count = self.headers.len();
+        let slot = self
+            .max_widths
+            .get_mut(column)
+            .ok_or(TableError::NoSuchColumn { column, count })?;
+        *slot = Some(width);
+        Ok(())
+    }
+
+    pub fn set_border(&mut self, border: Border) {
+        self.border = border;
+    }
+
+    // a row with fewer cells than headers gets empty cells at the end
+    pub fn add_row<S: ToString>(&mut self, cells: &[S]) -> Result<(), TableError> {
+        if cells.len() > self.headers.len() {
+            return Err(TableError::TooManyCells {
+                row: self.rows.len(),
+                cells: cells.len(),
+                columns: self.headers.len(),
+            });
+        }
+        let mut row = cells
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn render(&self) -> String {
+        let widths = self.column_widths();
+        let mut lines = Vec::new();
+
+        match self.border {
+            Border::None => {
+                lines.extend(self.render_row(&self.headers, &widths, "", "  ", ""));
+                let rule = widths
+                    .iter()
+                    .map(|&width| "-".repeat(width))
+                    .collect::<Vec<String>>();
+                lines.push(rule.join("  "));
+                for row in &self.rows {
+                    lines.extend(self.render_row(row, &widths, "", "  ", ""));
+                }
+            }
+            Border::Ascii | Border::Unicode => {
+                let chars = if self.border == Border::Ascii {
+                    &ASCII_BORDER
+                } else {
+                    &UNICODE_BORDER
+                };
+                let left = format!("{} ", chars.vertical);
+                let middle = format!(" {} ", chars.vertical);
+                let right = format!(" {}", chars.vertical);
+
+                lines.push(rule(&widths, chars.horizontal, chars.top));
+                lines.extend(self.render_row(&self.headers, &widths, &left, &middle, &right));
+                lines.push(rule(&widths, chars.horizontal, chars.middle));
+                for row in &self.rows {
+                    lines.extend(self.render_row(row, &widths, &left, &middle, &right));
+                }
+                lines.push(rule(&widths, chars.horizontal, chars.bottom));
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // cells don't wrap in Markdown: line breaks become <br> and '|' is escaped
+    pub fn to_markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+        let headers = self
+            .headers
+            .iter()
+            .map(|cell| escape(cell))
+            .collect::<Vec<String>>();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| escape(cell)).collect::<Vec<String>>())
+            .collect::<Vec<Vec<String>>>();
+
+        // at least 3 wide, so there is room for the alignment colons
+        let widths = (0..headers.len())
+            .map(|column| {
+                std::iter::once(&headers[column])
+                    .chain(rows.iter().map(|row| &row[column]))
+                    .map(|cell| layout::display_width(cell))
+                    .max()
+                    .unwrap_or(0)
+                    .max(3)
+            })
+            .collect::<Vec<usize>>();
+
+        let line = |cells: &[String]| {
+            let cells = cells
+                .iter()
+                .zip(&widths)
+                .zip(&self.aligns)
+                .map(|((cell, &width), &align)| layout::align(cell, width, align, ' '))
+                .collect::<Vec<String>>();
+            format!("| {} |", cells.join(" | "))
+        };
+        let separator = widths
+            .iter()
+            .zip(&self.aligns)
+            .map(|(&width, align)| match align {
+                Align::Left => format!(":{}", "-".repeat(width - 1)),
+                Align::Right => format!("{}:", "-".repeat(width - 1)),
+                Align::Center => format!(":{}:", "-".repeat(width - 2)),
+            })
+            .collect::<Vec<String>>();
+
+        let mut lines = vec![line(&headers), format!("| {} |", separator.join(" | "))];
+        lines.extend(rows.iter().map(|row| line(row)));
+        lines.join("\n")
+    }
+
+    // RFC 4180: fields with a comma, quote or line break are quoted, quotes are doubled
+    pub fn to_csv(&self) -> String {
+        let field = |cell: &String| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.clone()
+            }
+        };
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| row.iter().map(field).collect::<Vec<String>>().join(","))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .map(|column| {
+                let widest = std::iter::once(&self.headers[column])
+                    .chain(self.rows.iter().map(|row| &row[column]))
+                    .flat_map(|cell| cell.lines())
+                    .map(layout::display_width)
+                    .max()
+                    .unwrap_or(0);
+                match self.max_widths[column] {
+                    Some(max_width) => widest.min(max_width),
+                    None => widest,
+                }
+            })
+            .collect()
+    }
+
+    // one row can take several lines when its cells wrap
+    fn render_row(
+        &self,
+        cells: &[String],
+        widths: &[usize],
+        left: &str,
+        middle: &str,
+        right: &str,
+    ) -> Vec<String> {
+        let wrapped = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, &width)| layout::wrap(cell, width))
+            .collect::<Vec<Vec<String>>>();
+        let height = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+
+        (0..height)
+            .map(|line| {
+                let parts = wrapped
+                    .iter()
+                    .zip(widths)
+                    .zip(&self.aligns)
+                    .map(|((cell_lines, &width), &align)| {
+                        let text = cell_lines.get(line).map(String::as_str).unwrap_or("");
+                        layout::align(&layout::truncate(text, width), width, align, ' ')
+                    })
+                    .collect::<Vec<String>>();
+                format!("{left}{}{right}", parts.join(middle))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+// +------+-------+ with the given left, middle and right corners
+fn rule(widths: &[usize], horizontal: char, corners: [char; 3]) -> String {
+    let segments = widths
+        .iter()
+        .map(|&width| horizontal.to_string().repeat(width + 2))
+        .collect::<Vec<String>>();
+    format!(
+        "{}{}{}",
+        corners[0],
+        segments.join(&corners[1].to_string()),
+        corners[2]
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    NoSuchColumn {
+        column: usize,
+        count: usize,
+    },
+    TooManyCells {
+        row: usize,
+        cells: usize,
+        columns: usize,
+    },
+    WidthTooSmall {
+        column: usize,
+        width: usize,
+    },
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::NoSuchColumn { column, count } => {
+                write!(
+                    f,
+                    "there is no column {column}, the table has {count} columns"
+                )
+            }
+            TableError::TooManyCells {
+                row,
+                cells,
+                columns,
+            } => {
+                write!(
+                    f,
+                    "row {row} has {cells} cells but the table has {columns} columns"
+                )
+            }
+            TableError::WidthTooSmall { column, width } => {
+                write!(
+                    f,
+                    "column {column} can't be {width} wide, the least is {MIN_COLUMN_WIDTH}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for TableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cities() -> Table {
+        let mut table = Table::new(&["도시", "City", "Pop."]);
+        table.add_row(&["서울", "Seoul", "9668465"]).unwrap();
+        table.add_row(&["東京", "Tokyo"]).unwrap();
+        table
+    }
+
+    #[test]
+    fn lines_up_wide_chars() {
+        let mut table = cities();
+        table.set_align(2, Align::Right).unwrap();
+        assert_eq!(
+            table.render(),
+            [
+                "+------+-------+---------+",
+                "| 도시 | City  |    Pop. |",
+                "+------+-------+---------+",
+                "| 서울 | Seoul | 9668465 |",
+                "| 東京 | Tokyo |         |",
+                "+------+-------+---------+",
+            ]
+            .join("\n")
+        );
+        table.set_border(Border::Unicode);
+        assert!(table
+            .render()
+            .starts_with("┌──────┬───────┬─────────┐\n│ 도시 │ City  │    Pop. │"));
+        table.set_border(Border::None);
+        assert_eq!(table.render().lines().nth(1), Some("----  -----  -------"));
+    }
+
+    #[test]
+    fn centres_cells() {
+        let mut table = Table::new(&["name"]);
+        table.set_align(0, Align::Center).unwrap();
+        table.add_row(&["ab"]).unwrap();
+        table.add_row(&["é"]).unwrap();
+        table.set_border(Border::None);
+        assert_eq!(table.render(), "name\n----\n ab\n é");
+        table.add_row(&["e\u{301}"]).unwrap();
+        assert_eq!(table.render().lines().last(), Some(" e\u{301}"));
+    }
+
+    #[test]
+    fn wraps_at_the_max_width() {
+        let mut table = Table::new(&["Year", "Note"]);
+        table.set_max_width(1, 10).unwrap();
+        table.add_row(&["1372", "first count of the town"]).unwrap();
+        table.add_row(&["2020", "東京都庁舎の展望台"]).unwrap();
+        table.set_border(Border::None);
+        assert_eq!(
+            table.render(),
+            [
+                "Year  Note",
+                "----  ----------",
+                "1372  first",
+                "      count of",
+                "      the town",
+                "2020  東京都庁舎",
+                "      の展望台",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn keeps_every_char_at_the_narrowest_width() {
+        let mut table = Table::new(&["x"]);
+        table.set_max_width(0, MIN_COLUMN_WIDTH).unwrap();
+        table.add_row(&["東京abc"]).unwrap();
+        table.set_border(Border::None);
+        let lines = table
+            .render()
+            .lines()
+            .skip(2)
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        assert_eq!(lines, ["東", "京", "ab", "c"]);
+    }
+
+    #[test]
+    fn rejects_bad_columns_and_widths() {
+        let mut table = cities();
+        assert_eq!(
+            table.set_max_width(0, 1),
+            Err(TableError::WidthTooSmall {
+                column: 0,
+                width: 1
+            })
+        );
+        assert_eq!(
+            table.set_max_width(0, 0),
+            Err(TableError::WidthTooSmall {
+                column: 0,
+                width: 0
+            })
+        );
+        assert_eq!(
+            table.set_max_width(3, 10),
+            Err(TableError::NoSuchColumn {
+                column: 3,
+                count: 3
+            })
+        );
+        assert_eq!(
+            table.set_align(7, Align::Left),
+            Err(TableError::NoSuchColumn {
+                column: 7,
+                count: 3
+            })
+        );
+        assert_eq!(
+            table.add_row(&["a", "b", "c", "d"]),
+            Err(TableError::TooManyCells {
+                row: 2,
+                cells: 4,
+                columns: 3
+            })
+        );
+        assert_eq!(table.len(), 2);
+        assert!(Table::new(&["a"]).is_empty());
+    }
+
+    #[test]
+    fn markdown_and_csv() {
+        let mut table = Table::new(&["City", "Note"]);
+        table.set_align(1, Align::Right).unwrap();
+        table.add_row(&["Zürich", "a|b"]).unwrap();
+        table.add_row(&["서울", "one, \"two\"\nthree"]).unwrap();
+        assert_eq!(
+            table.to_markdown(),
+            [
+                "| City   |                Note |",
+                "| :----- | ------------------: |",
+                "| Zürich |                a\\|b |",
+                "| 서울   | one, \"two\"<br>three |",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            table.to_csv(),
+            "City,Note\nZürich,a|b\n서울,\"one, \"\"two\"\"\nthree\""
+        );
+    }
+}
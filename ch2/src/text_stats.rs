@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::layout;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+// TextStats : how big a piece of text is, counted in different ways
+// - bytes : `len()`, what the text takes in memory as UTF-8
+// - chars : `chars().count()`, Unicode scalar values
+// - graphemes : what a reader sees as one character, like 'é' made of 'e' + '\u{301}'
+// - words, sentences and lines : see the functions below
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextStats {
+    pub bytes: usize,
+    pub chars: usize,
+    pub graphemes: usize,
+    pub words: usize,
+    pub sentences: usize,
+    pub lines: usize,
+}
+
+impl TextStats {
+    pub fn of(text: &str) -> Self {
+        Self {
+            bytes: text.len(),
+            chars: text.chars().count(),
+            graphemes: graphemes(text).len(),
+            words: words(text).len(),
+            sentences: sentence_count(text),
+            lines: text.lines().count(),
+        }
+    }
+}
+
+impl fmt::Display for TextStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes, {} chars, {} graphemes, {} words, {} sentences, {} lines",
+            self.bytes, self.chars, self.graphemes, self.words, self.sentences, self.lines
+        )
+    }
+}
+
+// splits text into grapheme clusters, close to what Unicode's extended grapheme clusters give
+// - combining marks, variation selectors and emoji skin tones stay with the char before them
+// - emoji joined with a zero width joiner are one cluster, so are flags (two regional indicators)
+// - "\r\n" and Hangul jamo that make up one syllable are one cluster
+pub fn graphemes(text: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    // regional indicators pair up, so the third one starts a new flag
+    let mut regional_indicators = 0;
+
+    for (index, c) in text.char_indices() {
+        let joins = match previous {
+            None => false,
+            Some(before) => joins_previous(before, c, regional_indicators),
+        };
+        if !joins && index > start {
+            clusters.push(&text[start..index]);
+            start = index;
+        }
+        regional_indicators = if is_regional_indicator(c) {
+            if joins {
+                0
+            } else {
+                1
+            }
+        } else {
+            0
+        };
+        previous = Some(c);
+    }
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    clusters
+}
+
+// splits text into words
+// - a word is a run of letters and numbers, an apostrophe or hyphen inside a word keeps it together
+// - Chinese and Japanese don't put spaces between words and there is no dictionary here,
+//   so every Han ideograph and every hiragana char is counted as a word of its own: "東京" is two words
+// - a run of katakana is one word, since it is mostly one borrowed word: "タワー" is one word
+// - Korean puts spaces between words, so Hangul is treated like Latin letters
+pub fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if is_katakana(c) {
+            if let Some(word_start) = start.take() {
+                words.push(&text[word_start..index]);
+            }
+            while chars
+                .next_if(|&(_, next)| is_katakana(next) || is_combining(next))
+                .is_some()
+            {}
+            let end = chars.peek().map_or(text.len(), |&(next, _)| next);
+            words.push(&text[index..end]);
+            continue;
+        }
+        if is_ideographic(c) {
+            if let Some(word_start) = start.take() {
+                words.push(&text[word_start..index]);
+            }
+            let end = chars.peek().map_or(text.len(), |&(next, _)| next);
+            words.push(&text[index..end]);
+            continue;
+        }
+
+        let next = chars.peek().map(|&(_, next)| next);
+        let in_word = c.is_alphanumeric()
+            || (start.is_some() && is_combining(c))
+            || (start.is_some()
+                && matches!(c, '\'' | '’' | '-')
+                && next.is_some_and(char::is_alphanumeric));
+        match (in_word, start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                words.push(&text[word_start..index]);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        words.push(&text[word_start..]);
+    }
+    words
+}
+
+// counts sentences ending in . ! ? (and their CJK forms) followed by a space or the end
+// - "Really?!" and "Wait..." end one sentence, not several
+// - text after the last end mark counts as a sentence too
+// - abbreviations like "Dr." are counted as sentence ends
+pub fn sentence_count(text: &str) -> usize {
+    let mut sentences = 0;
+    let mut has_words = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() {
+            has_words = true;
+            continue;
+        }
+        if !is_sentence_end(c) || !has_words {
+            continue;
+        }
+        while chars.peek().copied().is_some_and(|next| {
+            is_sentence_end(next) || matches!(next, '"' | '\'' | ')' | '」' | '』')
+        }) {
+            chars.next();
+        }
+        // CJK end marks don't need a space after them
+        let ends = is_wide_sentence_end(c) || chars.peek().is_none_or(|next| next.is_whitespace());
+        if ends {
+            sentences += 1;
+            has_words = false;
+        }
+    }
+    if has_words {
+        sentences += 1;
+    }
+    sentences
+}
+
+// the `count` most used words, lowercased, most used first and in alphabetical order on a tie
+pub fn most_frequent_words(text: &str, count: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in words(text) {
+        *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+    let mut counts = counts.into_iter().collect::<Vec<(String, usize)>>();
+    counts.sort_by(|(a_word, a_count), (b_word, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_word.cmp(b_word))
+    });
+    counts.truncate(count);
+    counts
+}
+
+fn joins_previous(before: char, c: char, regional_indicators: usize) -> bool {
+    if before == '\r' && c == '\n' {
+        return true;
+    }
+    if before.is_control() || c.is_control() {
+        return false;
+    }
+    if before == ZERO_WIDTH_JOINER || is_combining(c) || is_emoji_modifier(c) {
+        return true;
+    }
+    if is_regional_indicator(before) && is_regional_indicator(c) {
+        return regional_indicators == 1;
+    }
+    hangul_joins(before, c)
+}
+
+// leading consonants join vowels and whole syllables, vowels join vowels and final consonants
+fn hangul_joins(before: char, c: char) -> bool {
+    let leading =
+        |c: char| ('\u{1100}'..='\u{115F}').contains(&c) || ('\u{A960}'..='\u{A97C}').contains(&c);
+    let vowel =
+        |c: char| ('\u{1160}'..='\u{11A7}').contains(&c) || ('\u{D7B0}'..='\u{D7C6}').contains(&c);
+    let trailing =
+        |c: char| ('\u{11A8}'..='\u{11FF}').contains(&c) || ('\u{D7CB}'..='\u{D7FB}').contains(&c);
+    let syllable = |c: char| ('\u{AC00}'..='\u{D7A3}').contains(&c);
+    // syllables with no final consonant are every 28th one from 가
+    let open_syllable = |c: char| syllable(c) && (c as u32 - 0xAC00).is_multiple_of(28);
+
+    (leading(before) && (leading(c) || vowel(c) || syllable(c)))
+        || ((vowel(before) || open_syllable(before)) && (vowel(c) || trailing(c)))
+        || ((trailing(before) || syllable(before)) && trailing(c))
+}
+
+// marks that attach to the char before them: zero width ones and spacing marks like 'ि'
+fn is_combining(c: char) -> bool {
+    c == ZERO_WIDTH_JOINER
+        || layout::is_spacing_mark(c)
+        || (c != '\u{200B}' && c != '\u{FEFF}' && !c.is_control() && layout::char_width(c) == 0)
+}
+
+fn is_emoji_modifier(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+// Han ideographs and hiragana, one word per char
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{3FFFD}')
+}
+
+// katakana, with the long vowel mark 'ー' and the halfwidth forms
+// - the middle dot '・' separates katakana words, so it isn't part of one
+fn is_katakana(c: char) -> bool {
+    c != '・'
+        && matches!(c, '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}')
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…') || is_wide_sentence_end(c)
+}
+
+fn is_wide_sentence_end(c: char) -> bool {
+    matches!(c, '。' | '！' | '？' | '｡')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn katakana_runs_are_one_word() {
+        assert_eq!(words("東京タワー"), ["東", "京", "タワー"]);
+        assert_eq!(words("ｺｰﾋｰを飲む"), ["ｺｰﾋｰ", "を", "飲", "む"]);
+        // a voiced sound mark written as a combining char stays in the word
+        assert_eq!(words("ハ\u{309A}ン"), ["ハ\u{309A}ン"]);
+        assert_eq!(words("ラーメン・カレー"), ["ラーメン", "カレー"]);
+    }
+
+    #[test]
+    fn mixed_cjk_latin_and_hangul() {
+        assert_eq!(
+            words("東京タワー is Tokyo's tower, 서울타워 is Seoul's"),
+            [
+                "東",
+                "京",
+                "タワー",
+                "is",
+                "Tokyo's",
+                "tower",
+                "서울타워",
+                "is",
+                "Seoul's"
+            ]
+        );
+        assert_eq!(words("iPhoneを買った"), ["iPhone", "を", "買", "っ", "た"]);
+        assert_eq!(words("中文English한국어"), ["中", "文", "English한국어"]);
+        assert_eq!(
+            words("Rustプログラミング入門 2판"),
+            ["Rust", "プログラミング", "入", "門", "2판"]
+        );
+    }
+
+    #[test]
+    fn spacing_marks_stay_with_their_consonant() {
+        assert_eq!(graphemes("किताब"), ["कि", "ता", "ब"]);
+        assert_eq!(words("हिंदी किताब"), ["हिंदी", "किताब"]);
+    }
+
+    #[test]
+    fn stats_of_mixed_text() {
+        let stats = TextStats::of("안녕하세요. 東京タワー！ Hello\r\nworld");
+        assert_eq!(stats.words, 6);
+        assert_eq!(stats.sentences, 3);
+        assert_eq!(stats.lines, 2);
+        assert_eq!(stats.graphemes, stats.chars - 1);
+    }
+}
@@ -0,0 +1,503 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// the CSS names for some common colors
+pub const NAMED_COLORS: [(&str, Rgb); 32] = [
+    ("black", Rgb::new(0, 0, 0)),
+    ("white", Rgb::new(255, 255, 255)),
+    ("gray", Rgb::new(128, 128, 128)),
+    ("silver", Rgb::new(192, 192, 192)),
+    ("dimgray", Rgb::new(105, 105, 105)),
+    ("red", Rgb::new(255, 0, 0)),
+    ("maroon", Rgb::new(128, 0, 0)),
+    ("crimson", Rgb::new(220, 20, 60)),
+    ("salmon", Rgb::new(250, 128, 114)),
+    ("pink", Rgb::new(255, 192, 203)),
+    ("orange", Rgb::new(255, 165, 0)),
+    ("coral", Rgb::new(255, 127, 80)),
+    ("gold", Rgb::new(255, 215, 0)),
+    ("yellow", Rgb::new(255, 255, 0)),
+    ("olive", Rgb::new(128, 128, 0)),
+    ("khaki", Rgb::new(240, 230, 140)),
+    ("lime", Rgb::new(0, 255, 0)),
+    ("green", Rgb::new(0, 128, 0)),
+    ("darkgreen", Rgb::new(0, 100, 0)),
+    ("teal", Rgb::new(0, 128, 128)),
+    ("cyan", Rgb::new(0, 255, 255)),
+    ("turquoise", Rgb::new(64, 224, 208)),
+    ("skyblue", Rgb::new(135, 206, 235)),
+    ("blue", Rgb::new(0, 0, 255)),
+    ("navy", Rgb::new(0, 0, 128)),
+    ("royalblue", Rgb::new(65, 105, 225)),
+    ("indigo", Rgb::new(75, 0, 130)),
+    ("purple", Rgb::new(128, 0, 128)),
+    ("violet", Rgb::new(238, 130, 238)),
+    ("magenta", Rgb::new(255, 0, 255)),
+    ("brown", Rgb::new(165, 42, 42)),
+    ("chocolate", Rgb::new(210, 105, 30)),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+// hue in degrees (0..360), the rest from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+
+// large text is at least 18pt, or 14pt and bold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSize {
+    Normal,
+    Large,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WcagLevel {
+    Fail,
+    Aa,
+    Aaa,
+}
+
+impl Rgb {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    // "#1e90ff", "1E90FF" or the short "#fff"
+    pub fn from_hex(hex: &str) -> Result<Self, ColorError> {
+        let invalid = || ColorError::InvalidHex(hex.to_string());
+        let digits = hex.trim();
+        let digits = digits.strip_prefix('#').unwrap_or(digits);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |text: &str| u8::from_str_radix(text, 16).map_err(|_| invalid());
+
+        match digits.len() {
+            3 => {
+                // "f80" is short for "ff8800"
+                let doubled = digits.chars().flat_map(|c| [c, c]).collect::<String>();
+                Self::from_hex(&doubled)
+            }
+            6 => Ok(Self::new(
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    pub fn to_hsl(self) -> Hsl {
+        let (hue, max, min) = self.hue_max_min();
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Hsl {
+            hue,
+            saturation,
+            lightness,
+        }
+    }
+
+    pub fn from_hsl(hsl: Hsl) -> Self {
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * hsl.saturation.clamp(0.0, 1.0);
+        Self::from_hue_chroma(hsl.hue, chroma, lightness - chroma / 2.0)
+    }
+
+    pub fn to_hsv(self) -> Hsv {
+        let (hue, max, min) = self.hue_max_min();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Hsv {
+            hue,
+            saturation,
+            value: max,
+        }
+    }
+
+    pub fn from_hsv(hsv: Hsv) -> Self {
+        let value = hsv.value.clamp(0.0, 1.0);
+        let chroma = value * hsv.saturation.clamp(0.0, 1.0);
+        Self::from_hue_chroma(hsv.hue, chroma, value - chroma)
+    }
+
+    // how bright the color looks to people, from 0.0 (black) to 1.0 (white), as WCAG 2 defines it
+    pub fn relative_luminance(&self) -> f64 {
+        let linear = |channel: u8| {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+    }
+
+    // from 1.0 (no contrast) to 21.0 (black on white), the order of the colors doesn't matter
+    pub fn contrast_ratio(&self, other: &Rgb) -> f64 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    pub fn wcag_level(&self, background: &Rgb, size: TextSize) -> WcagLevel {
+        let ratio = self.contrast_ratio(background);
+        let (aa, aaa) = match size {
+            TextSize::Normal => (4.5, 7.0),
+            TextSize::Large => (3.0, 4.5),
+        };
+        if ratio >= aaa {
+            WcagLevel::Aaa
+        } else if ratio >= aa {
+            WcagLevel::Aa
+        } else {
+            WcagLevel::Fail
+        }
+    }
+
+    // the closest color in NAMED_COLORS, using the "redmean" distance
+    // which is closer to what eyes see than plain RGB distance
+    pub fn nearest_named(&self) -> (&'static str, Rgb) {
+        NAMED_COLORS
+            .iter()
+            .copied()
+            .min_by(|(_, a), (_, b)| self.distance(a).total_cmp(&self.distance(b)))
+            .expect("there are named colors")
+    }
+
+    pub fn complementary(&self) -> Rgb {
+        self.rotate_hue(180.0)
+    }
+
+    // the color and the two that are a third of the way around the color wheel
+    pub fn triadic(&self) -> [Rgb; 3] {
+        [*self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    // the color with its neighbours `step` degrees away on both sides
+    pub fn analogous(&self, step: f64) -> [Rgb; 3] {
+        [self.rotate_hue(-step), *self, self.rotate_hue(step)]
+    }
+
+    // `count` colors from this one towards black
+    pub fn shades(&self, count: usize) -> Vec<Rgb> {
+        self.mix_steps(Rgb::new(0, 0, 0), count)
+    }
+
+    // `count` colors from this one towards white
+    pub fn tints(&self, count: usize) -> Vec<Rgb> {
+        self.mix_steps(Rgb::new(255, 255, 255), count)
+    }
+
+    fn rotate_hue(&self, degrees: f64) -> Rgb {
+        let mut hsl = self.to_hsl();
+        hsl.hue = (hsl.hue + degrees).rem_euclid(360.0);
+        Rgb::from_hsl(hsl)
+    }
+
+    fn mix_steps(&self, target: Rgb, count: usize) -> Vec<Rgb> {
+        let mix = |from: u8, to: u8, amount: f64| {
+            (f64::from(from) + (f64::from(to) - f64::from(from)) * amount).round() as u8
+        };
+        (0..count)
+            .map(|i| i as f64 / count as f64)
+            .map(|amount| {
+                Rgb::new(
+                    mix(self.red, target.red, amount),
+                    mix(self.green, target.green, amount),
+                    mix(self.blue, target.blue, amount),
+                )
+            })
+            .collect()
+    }
+
+    fn distance(&self, other: &Rgb) -> f64 {
+        let mean_red = (f64::from(self.red) + f64::from(other.red)) / 2.0;
+        let red = f64::from(self.red) - f64::from(other.red);
+        let green = f64::from(self.green) - f64::from(other.green);
+        let blue = f64::from(self.blue) - f64::from(other.blue);
+        ((2.0 + mean_red / 256.0) * red * red
+            + 4.0 * green * green
+            + (2.0 + (255.0 - mean_red) / 256.0) * blue * blue)
+            .sqrt()
+    }
+
+    // hue in degrees, and the biggest and smallest channel from 0.0 to 1.0
+    fn hue_max_min(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            f64::from(self.red) / 255.0,
+            f64::from(self.green) / 255.0,
+            f64::from(self.blue) / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, max, min)
+    }
+
+    // shared by HSL and HSV: `offset` is added to every channel
+    fn from_hue_chroma(hue: f64, chroma: f64, offset: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |c: f64| ((c + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self::new(channel(r), channel(g), channel(b))
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+// a hex code or one of NAMED_COLORS
+impl FromStr for Rgb {
+    type Err = ColorError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let name = input.trim().to_lowercase();
+        match NAMED_COLORS.iter().find(|(named, _)| *named == name) {
+            Some((_, rgb)) => Ok(*rgb),
+            None => Rgb::from_hex(input),
+        }
+    }
+}
+
+// the (red, green, blue) tuples that `match_colors` takes
+impl TryFrom<(i32, i32, i32)> for Rgb {
+    type Error = ColorError;
+
+    fn try_from((red, green, blue): (i32, i32, i32)) -> Result<Self, Self::Error> {
+        let channel =
+            |value: i32| u8::try_from(value).map_err(|_| ColorError::ChannelOutOfRange(value));
+        Ok(Rgb::new(channel(red)?, channel(green)?, channel(blue)?))
+    }
+}
+
+impl From<Rgb> for (i32, i32, i32) {
+    fn from(rgb: Rgb) -> Self {
+        (rgb.red.into(), rgb.green.into(), rgb.blue.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    InvalidHex(String),
+    ChannelOutOfRange(i32),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorError::InvalidHex(input) => write!(f, "{input:?} is not a color like #1e90ff"),
+            ColorError::ChannelOutOfRange(value) => write!(f, "{value} is not between 0 and 255"),
+        }
+    }
+}
+
+impl Error for ColorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(input: &str) -> Rgb {
+        input.parse().unwrap()
+    }
+
+    fn ratio(a: &str, b: &str) -> f64 {
+        (rgb(a).contrast_ratio(&rgb(b)) * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn contrast_of_known_pairs() {
+        assert_eq!(rgb("black").contrast_ratio(&rgb("white")), 21.0);
+        assert_eq!(rgb("white").contrast_ratio(&rgb("black")), 21.0);
+        assert_eq!(rgb("#1e90ff").contrast_ratio(&rgb("#1e90ff")), 1.0);
+        assert_eq!(ratio("#767676", "white"), 4.54);
+        assert_eq!(ratio("#777777", "white"), 4.48);
+        assert_eq!(ratio("red", "white"), 4.0);
+        assert_eq!(ratio("blue", "white"), 8.59);
+        assert_eq!(rgb("white").relative_luminance(), 1.0);
+        assert_eq!(rgb("black").relative_luminance(), 0.0);
+    }
+
+    #[test]
+    fn wcag_levels() {
+        use TextSize::*;
+        let white = rgb("white");
+        assert_eq!(rgb("black").wcag_level(&white, Normal), WcagLevel::Aaa);
+        // #767676 is the lightest gray that passes AA on white, #777777 just misses it
+        assert_eq!(rgb("#767676").wcag_level(&white, Normal), WcagLevel::Aa);
+        assert_eq!(rgb("#777777").wcag_level(&white, Normal), WcagLevel::Fail);
+        assert_eq!(rgb("#777777").wcag_level(&white, Large), WcagLevel::Aa);
+        assert_eq!(rgb("#595959").wcag_level(&white, Normal), WcagLevel::Aaa);
+        assert_eq!(rgb("yellow").wcag_level(&white, Large), WcagLevel::Fail);
+    }
+
+    #[test]
+    fn reads_hex_and_names() {
+        assert_eq!(Rgb::from_hex("#1e90ff"), Ok(Rgb::new(30, 144, 255)));
+        assert_eq!(Rgb::from_hex("1E90FF"), Ok(Rgb::new(30, 144, 255)));
+        assert_eq!(Rgb::from_hex(" #f80 "), Ok(Rgb::new(255, 136, 0)));
+        assert_eq!(rgb("  Crimson "), Rgb::new(220, 20, 60));
+        assert_eq!(Rgb::new(30, 144, 255).to_hex(), "#1e90ff");
+        assert_eq!(rgb("#ABCDEF").to_string(), "#abcdef");
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        for input in [
+            "",
+            "#",
+            "#12345",
+            "#1234567",
+            "#ggg",
+            "#12 345",
+            "##fff",
+            "+1e90ff",
+            "#1e90fé",
+            "dodgerblue",
+        ] {
+            assert_eq!(
+                input.parse::<Rgb>(),
+                Err(ColorError::InvalidHex(input.to_string())),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            Rgb::from_hex("#12345").unwrap_err().to_string(),
+            "\"#12345\" is not a color like #1e90ff"
+        );
+    }
+
+    #[test]
+    fn rgb_tuples_have_to_fit_in_a_byte() {
+        assert_eq!(Rgb::try_from((0, 128, 255)), Ok(Rgb::new(0, 128, 255)));
+        assert_eq!(
+            Rgb::try_from((256, 0, 0)),
+            Err(ColorError::ChannelOutOfRange(256))
+        );
+        assert_eq!(
+            Rgb::try_from((0, -1, 300)),
+            Err(ColorError::ChannelOutOfRange(-1))
+        );
+        assert_eq!(
+            ColorError::ChannelOutOfRange(-1).to_string(),
+            "-1 is not between 0 and 255"
+        );
+        assert_eq!(<(i32, i32, i32)>::from(Rgb::new(1, 2, 3)), (1, 2, 3));
+    }
+
+    #[test]
+    fn hsl_and_hsv() {
+        let hsl = rgb("#1e90ff").to_hsl();
+        assert_eq!(hsl.hue.round(), 210.0);
+        assert_eq!((hsl.saturation * 100.0).round(), 100.0);
+        assert_eq!((hsl.lightness * 100.0).round(), 56.0);
+        assert_eq!(rgb("gray").to_hsl().saturation, 0.0);
+        assert_eq!(
+            rgb("black").to_hsv(),
+            Hsv {
+                hue: 0.0,
+                saturation: 0.0,
+                value: 0.0
+            }
+        );
+
+        // out of range values are wrapped or clamped instead of giving an error
+        let red = Hsl {
+            hue: 360.0,
+            saturation: 2.0,
+            lightness: 0.5,
+        };
+        assert_eq!(Rgb::from_hsl(red), rgb("red"));
+        assert_eq!(Rgb::from_hsl(Hsl { hue: -120.0, ..red }), rgb("blue"));
+        assert_eq!(
+            Rgb::from_hsl(Hsl {
+                lightness: f64::NAN,
+                ..red
+            }),
+            rgb("black")
+        );
+        assert_eq!(
+            Rgb::from_hsv(Hsv {
+                hue: 120.0,
+                saturation: 1.0,
+                value: 1.5
+            }),
+            rgb("lime")
+        );
+    }
+
+    #[test]
+    fn every_color_round_trips() {
+        for red in (0..=255).step_by(15) {
+            for green in (0..=255).step_by(17) {
+                for blue in (0..=255).step_by(51) {
+                    let color = Rgb::new(red, green, blue);
+                    assert_eq!(Rgb::from_hsl(color.to_hsl()), color);
+                    assert_eq!(Rgb::from_hsv(color.to_hsv()), color);
+                    assert_eq!(Rgb::from_hex(&color.to_hex()), Ok(color));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn palettes() {
+        assert_eq!(rgb("red").complementary(), rgb("cyan"));
+        assert_eq!(rgb("red").triadic(), [rgb("red"), rgb("lime"), rgb("blue")]);
+        assert_eq!(
+            rgb("red").analogous(60.0),
+            [rgb("magenta"), rgb("red"), rgb("yellow")]
+        );
+        assert_eq!(rgb("white").shades(2), [rgb("white"), rgb("gray")]);
+        assert_eq!(rgb("black").tints(4)[1], Rgb::new(64, 64, 64));
+        assert!(rgb("red").shades(0).is_empty());
+        assert_eq!(Rgb::new(250, 10, 70).nearest_named().0, "crimson");
+        assert_eq!(rgb("navy").nearest_named(), ("navy", rgb("navy")));
+    }
+}
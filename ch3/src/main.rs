@@ -1,3 +1,11 @@
+use color::{Hsv, Rgb, TextSize};
+use number_culture::{NumberBelief, NumberTable, Sentiment};
+use tracked_vec::TrackedVec;
+
+mod color;
+mod number_culture;
+mod tracked_vec;
+
 fn main() {
     // arrays : simple, fast, immutable collections of the same type
     // vectors : similar to arrays but growable and with more functionality
@@ -46,10 +54,13 @@ fn main() {
     let start_at_one = &array_of_ten[1..];
     let end_at_five = &array_of_ten[..5];
     let everything = &array_of_ten[..];
-    println!("two_to_five: {:?}
+    println!(
+        "two_to_five: {:?}
 start_at_one: {:?}
 end_at_five: {:?}
-everything: {:?}", two_to_five, start_at_one, end_at_five, everything);
+everything: {:?}",
+        two_to_five, start_at_one, end_at_five, everything
+    );
 
     // Vec::new()
     let mut my_vec = Vec::new();
@@ -74,6 +85,56 @@ everything: {:?}", two_to_five, start_at_one, end_at_five, everything);
     let mut num_vec = Vec::with_capacity(8);
     num_vec.push('a');
 
+    // TrackedVec writes down every reallocation, to see where with_capacity pays off
+    let mut tracked: TrackedVec<String> = TrackedVec::new();
+    for name in ["shou", "anna", "foo", "bar", "ai"] {
+        tracked.push(name.to_string());
+    }
+    for reallocation in tracked.reallocations() {
+        println!(
+            "capacity {} -> {}, copied {} bytes",
+            reallocation.old_capacity, reallocation.new_capacity, reallocation.bytes_copied
+        );
+    }
+    let report = tracked.report();
+    println!("{report}");
+    let mut presized: TrackedVec<String> = TrackedVec::with_capacity(report.peak_len);
+    presized.extend(tracked.iter().cloned());
+    println!(
+        "with capacity {}: {}",
+        presized.capacity(),
+        presized.report()
+    );
+
+    let mut numbers = (1..=100).collect::<TrackedVec<u64>>();
+    numbers.clear_history();
+    numbers.extend((0..1_000).filter(|number| number % 3 == 0));
+    numbers.extend_from_slice(&[7, 8, 9]);
+    numbers.extend(&[10, 11]);
+    numbers.extend_from_within(..50);
+    numbers.insert(0, 0);
+    numbers.append(&mut vec![1; 500]);
+    numbers.resize(2_000, 2);
+    numbers.retain(|number| number % 2 == 0);
+    numbers.truncate(100);
+    numbers.shrink_to_fit();
+    numbers.sort_unstable();
+    numbers.dedup();
+    let evens = numbers.drain(..5).collect::<Vec<u64>>();
+    let tail = numbers.split_off(numbers.len() / 2);
+    println!("{evens:?} and {} more split off", tail.len());
+    let (last, first, second) = (numbers.pop(), numbers.remove(0), numbers.swap_remove(0));
+    println!("{last:?} {first} {second} {:?}", numbers.first());
+    numbers.reserve(10);
+    numbers.reserve_exact(1_000);
+    println!("{}", numbers.report());
+    numbers.clear();
+    println!(
+        "{} items, {} left in the Vec",
+        numbers.len(),
+        numbers.into_inner().capacity()
+    );
+
     // into()
     // make array to vec
     let my_vec: Vec<u8> = [1, 2, 3].into();
@@ -215,6 +276,111 @@ Sixth item: {:?}",
         }
     };
     println!("{my_number}");
+
+    // colors : a real color type instead of a tuple of i32
+    let dodger_blue = Rgb::from_hex("#1e90ff").unwrap();
+    let hsl = dodger_blue.to_hsl();
+    println!(
+        "{dodger_blue} is hue {:.0}, saturation {:.2}, lightness {:.2}",
+        hsl.hue, hsl.saturation, hsl.lightness
+    );
+    println!("and back again: {}", Rgb::from_hsl(hsl));
+    let hsv = dodger_blue.to_hsv();
+    println!(
+        "as HSV: {hsv:?}, and back again: {}",
+        Rgb::from_hsv(Hsv { value: 0.5, ..hsv })
+    );
+
+    for (text, background) in [("#777", "white"), ("black", "yellow"), ("#1e90ff", "navy")] {
+        let (text, background) = (
+            text.parse::<Rgb>().unwrap(),
+            background.parse::<Rgb>().unwrap(),
+        );
+        println!(
+            "{text} on {background}: contrast {:.2}, {:?} for normal text, {:?} for large text",
+            text.contrast_ratio(&background),
+            text.wcag_level(&background, TextSize::Normal),
+            text.wcag_level(&background, TextSize::Large),
+        );
+    }
+
+    let (name, named) = Rgb::new(250, 10, 70).nearest_named();
+    println!("#fa0a46 is closest to {name} ({named})");
+    println!("complementary: {}", dodger_blue.complementary());
+    println!(
+        "triadic: {:?}",
+        dodger_blue.triadic().map(|rgb| rgb.to_hex())
+    );
+    println!(
+        "analogous: {:?}",
+        dodger_blue.analogous(30.0).map(|rgb| rgb.to_hex())
+    );
+    println!(
+        "shades: {:?}",
+        dodger_blue
+            .shades(4)
+            .iter()
+            .map(|rgb| rgb.to_hex())
+            .collect::<Vec<String>>()
+    );
+    println!(
+        "tints: {:?}",
+        dodger_blue
+            .tints(4)
+            .iter()
+            .map(|rgb| rgb.to_hex())
+            .collect::<Vec<String>>()
+    );
+
+    for rgb in [(5, 200, 80), (300, 0, 0)] {
+        match Rgb::try_from(rgb) {
+            Ok(color) => match_colors_better_version(color.into()),
+            Err(e) => println!("{e}"),
+        }
+    }
+    println!("{:?}", Rgb::from_hex("#12345"));
+
+    // numbers : what they mean in different cultures, and how to say them
+    let mut number_table = NumberTable::default();
+    number_table.add(NumberBelief {
+        culture: "Thailand".to_string(),
+        first: 9,
+        last: 9,
+        sentiment: Sentiment::Lucky,
+        meaning: "sounds like the word for progress".to_string(),
+    });
+    for number in [4, 9, 13, 17, 42] {
+        match_number(number);
+        let beliefs = number_table.lookup(number.into());
+        if beliefs.is_empty() {
+            println!(
+                "  {} doesn't mean anything special",
+                number_culture::to_words(number.into())
+            );
+        }
+        for belief in beliefs {
+            println!(
+                "  {} is {:?} in {}: {}",
+                number_culture::to_words(number.into()),
+                belief.sentiment,
+                belief.culture,
+                belief.meaning,
+            );
+        }
+    }
+    println!(
+        "Does 4 mean something in Korea? {:?}",
+        number_table.lookup_in(4, "korea").len()
+    );
+    println!("Cultures: {:?}", number_table.cultures());
+    for number in [0, 19, 105, 1_000_001, -2_024, i128::MAX] {
+        println!("{number}: {}", number_culture::to_words(number));
+    }
+    // in India, 12,34,567 is counted in lakhs and crores instead of millions
+    println!("12,34,567: {}", number_culture::to_words_indian(1_234_567));
+    if let Err(e) = NumberTable::parse("Germany,eleven,11,lucky,carnival starts on 11.11.") {
+        println!("{e}");
+    }
 }
 
 fn match_colors(rgb: (i32, i32, i32)) {
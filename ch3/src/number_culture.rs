@@ -0,0 +1,407 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+// short scale names for each group of three digits, enough for any i128
+const SCALES: [&str; 13] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+// the table that `NumberTable::default()` starts with, one belief per line:
+// culture, first number, last number, sentiment, meaning
+pub const DEFAULT_BELIEFS: &str = "\
+China,4,4,unlucky,sounds like the word for death
+China,6,6,lucky,sounds like the word for smooth
+China,8,8,lucky,sounds like the word for wealth
+China,9,9,lucky,sounds like the word for long-lasting
+Japan,4,4,unlucky,can be read as shi, which also means death
+Japan,9,9,unlucky,can be read as ku, which also means suffering
+Japan,7,7,lucky,there are seven gods of fortune
+Korea,4,4,unlucky,sounds like the word for death
+Italy,13,13,lucky,a number of prosperity and life
+Italy,17,17,unlucky,XVII can be rearranged into VIXI, I have lived
+United States,13,13,unlucky,many buildings have no 13th floor
+United States,7,7,lucky,lucky number seven
+English,13,19,neutral,ends with -teen";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sentiment {
+    Lucky,
+    Unlucky,
+    Neutral,
+}
+
+impl FromStr for Sentiment {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "lucky" => Ok(Sentiment::Lucky),
+            "unlucky" => Ok(Sentiment::Unlucky),
+            "neutral" => Ok(Sentiment::Neutral),
+            other => Err(format!("`{other}` should be lucky, unlucky or neutral")),
+        }
+    }
+}
+
+// what a range of numbers means to a culture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberBelief {
+    pub culture: String,
+    pub first: i128,
+    pub last: i128,
+    pub sentiment: Sentiment,
+    pub meaning: String,
+}
+
+// NumberTable : number superstitions and meanings for many cultures
+// the content team can keep their own table as text in the DEFAULT_BELIEFS format
+#[derive(Debug, Clone)]
+pub struct NumberTable {
+    beliefs: Vec<NumberBelief>,
+}
+
+impl Default for NumberTable {
+    fn default() -> Self {
+        Self::parse(DEFAULT_BELIEFS).expect("the default beliefs are valid")
+    }
+}
+
+impl NumberTable {
+    // the meaning can have commas in it, so it takes the rest of the line
+    pub fn parse(text: &str) -> Result<Self, TableError> {
+        let mut beliefs = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let error = |message: String| TableError {
+                line: line_number,
+                message,
+            };
+
+            let fields = line.splitn(5, ',').map(str::trim).collect::<Vec<&str>>();
+            let [culture, first, last, sentiment, meaning] = fields[..] else {
+                return Err(error(
+                    "expected culture,first,last,sentiment,meaning".to_string(),
+                ));
+            };
+            let number = |text: &str| {
+                text.parse::<i128>()
+                    .map_err(|_| error(format!("`{text}` is not a whole number")))
+            };
+            let (first, last) = (number(first)?, number(last)?);
+            if first > last {
+                return Err(error(format!("{first} comes after {last}")));
+            }
+
+            beliefs.push(NumberBelief {
+                culture: culture.to_string(),
+                first,
+                last,
+                sentiment: sentiment.parse().map_err(error)?,
+                meaning: meaning.to_string(),
+            });
+        }
+        Ok(Self { beliefs })
+    }
+
+    pub fn add(&mut self, belief: NumberBelief) {
+        self.beliefs.push(belief);
+    }
+
+    pub fn lookup(&self, number: i128) -> Vec<&NumberBelief> {
+        self.beliefs
+            .iter()
+            .filter(|belief| (belief.first..=belief.last).contains(&number))
+            .collect()
+    }
+
+    pub fn lookup_in(&self, number: i128, culture: &str) -> Vec<&NumberBelief> {
+        self.lookup(number)
+            .into_iter()
+            .filter(|belief| belief.culture.eq_ignore_ascii_case(culture))
+            .collect()
+    }
+
+    pub fn cultures(&self) -> Vec<&str> {
+        let mut cultures = self
+            .beliefs
+            .iter()
+            .map(|belief| belief.culture.as_str())
+            .collect::<Vec<&str>>();
+        cultures.sort();
+        cultures.dedup();
+        cultures
+    }
+}
+
+// 1_234 -> "one thousand two hundred thirty-four", -19 -> "minus nineteen"
+pub fn to_words(number: i128) -> String {
+    if number == 0 {
+        return ONES[0].to_string();
+    }
+
+    let mut rest = number.unsigned_abs();
+    let mut groups = Vec::new();
+    for scale in SCALES {
+        let group = (rest % 1000) as usize;
+        if group > 0 {
+            let words = three_digits_to_words(group);
+            groups.push(if scale.is_empty() {
+                words
+            } else {
+                format!("{words} {scale}")
+            });
+        }
+        rest /= 1000;
+        if rest == 0 {
+            break;
+        }
+    }
+    groups.reverse();
+    signed(number, groups.join(" "))
+}
+
+// the Indian system: after the first thousand, digits are grouped in twos
+// 1_234_567 -> "twelve lakh thirty-four thousand five hundred sixty-seven"
+// crores are counted with the same words, so 1_000_000_000 is "one hundred crore"
+pub fn to_words_indian(number: i128) -> String {
+    if number == 0 {
+        return ONES[0].to_string();
+    }
+    signed(number, indian_words(number.unsigned_abs()))
+}
+
+// 1..
+fn indian_words(number: u128) -> String {
+    let crores = number / 10_000_000;
+    let lakhs = (number / 100_000 % 100) as usize;
+    let thousands = (number / 1_000 % 100) as usize;
+    let rest = (number % 1_000) as usize;
+
+    let mut groups = Vec::new();
+    if crores > 0 {
+        groups.push(format!("{} crore", indian_words(crores)));
+    }
+    for (count, name) in [(lakhs, "lakh"), (thousands, "thousand")] {
+        if count > 0 {
+            groups.push(format!("{} {name}", three_digits_to_words(count)));
+        }
+    }
+    if rest > 0 {
+        groups.push(three_digits_to_words(rest));
+    }
+    groups.join(" ")
+}
+
+fn signed(number: i128, words: String) -> String {
+    if number < 0 {
+        format!("minus {words}")
+    } else {
+        words
+    }
+}
+
+// 1..=999
+fn three_digits_to_words(number: usize) -> String {
+    let (hundreds, rest) = (number / 100, number % 100);
+    let mut words = Vec::new();
+    if hundreds > 0 {
+        words.push(format!("{} hundred", ONES[hundreds]));
+    }
+    match rest {
+        0 => {}
+        1..=19 => words.push(ONES[rest].to_string()),
+        _ if rest % 10 == 0 => words.push(TENS[rest / 10].to_string()),
+        _ => words.push(format!("{}-{}", TENS[rest / 10], ONES[rest % 10])),
+    }
+    words.join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for TableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_up_to_a_thousand() {
+        let expected = [
+            (0, "zero"),
+            (7, "seven"),
+            (13, "thirteen"),
+            (19, "nineteen"),
+            (20, "twenty"),
+            (42, "forty-two"),
+            (99, "ninety-nine"),
+            (100, "one hundred"),
+            (105, "one hundred five"),
+            (110, "one hundred ten"),
+            (999, "nine hundred ninety-nine"),
+        ];
+        for (number, words) in expected {
+            assert_eq!(to_words(number), words);
+            assert_eq!(to_words_indian(number), words);
+        }
+    }
+
+    #[test]
+    fn short_scale_groups_by_thousands() {
+        assert_eq!(to_words(1_000), "one thousand");
+        assert_eq!(to_words(1_000_001), "one million one");
+        assert_eq!(
+            to_words(1_234_567),
+            "one million two hundred thirty-four thousand five hundred sixty-seven"
+        );
+        assert_eq!(to_words(10_000_000), "ten million");
+        assert_eq!(to_words(1_000_000_000_000), "one trillion");
+        assert!(to_words(i128::MAX).starts_with("one hundred seventy undecillion"));
+        assert!(to_words(i128::MAX).ends_with("seven hundred twenty-seven"));
+    }
+
+    #[test]
+    fn indian_groups_by_lakh_and_crore() {
+        assert_eq!(to_words_indian(1_000), "one thousand");
+        assert_eq!(
+            to_words_indian(99_999),
+            "ninety-nine thousand nine hundred ninety-nine"
+        );
+        assert_eq!(to_words_indian(100_000), "one lakh");
+        assert_eq!(to_words_indian(100_001), "one lakh one");
+        assert_eq!(
+            to_words_indian(1_234_567),
+            "twelve lakh thirty-four thousand five hundred sixty-seven"
+        );
+        assert_eq!(to_words_indian(10_000_000), "one crore");
+        assert_eq!(
+            to_words_indian(999_999_999),
+            "ninety-nine crore ninety-nine lakh ninety-nine thousand nine hundred ninety-nine"
+        );
+        assert_eq!(to_words_indian(1_000_000_000), "one hundred crore");
+        assert_eq!(to_words_indian(1_000_000_000_000), "one lakh crore");
+        assert_eq!(to_words_indian(100_000_000_000_000), "one crore crore");
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_eq!(to_words(-19), "minus nineteen");
+        assert_eq!(to_words(-2_024), "minus two thousand twenty-four");
+        assert_eq!(to_words_indian(-150_000), "minus one lakh fifty thousand");
+        // i128::MIN has no positive i128, but its size still fits in a u128
+        assert!(to_words(i128::MIN).starts_with("minus one hundred seventy undecillion"));
+        assert!(to_words(i128::MIN).ends_with("seven hundred twenty-eight"));
+        assert!(to_words_indian(i128::MIN)
+            .ends_with("crore forty-one lakh five thousand seven hundred twenty-eight"));
+    }
+
+    #[test]
+    fn looks_up_beliefs() {
+        let table = NumberTable::default();
+        let cultures = |number| {
+            table
+                .lookup(number)
+                .iter()
+                .map(|belief| belief.culture.as_str())
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(cultures(4), ["China", "Japan", "Korea"]);
+        assert_eq!(cultures(13), ["Italy", "United States", "English"]);
+        assert_eq!(cultures(16), ["English"]);
+        assert!(cultures(20).is_empty());
+        assert_eq!(table.lookup_in(13, "italy")[0].sentiment, Sentiment::Lucky);
+        assert_eq!(
+            table.lookup_in(13, "UNITED STATES")[0].sentiment,
+            Sentiment::Unlucky
+        );
+        assert!(table.lookup_in(4, "Italy").is_empty());
+        assert_eq!(
+            table.cultures(),
+            [
+                "China",
+                "English",
+                "Italy",
+                "Japan",
+                "Korea",
+                "United States"
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_tables() {
+        let table = NumberTable::parse(
+            "# comment\n\n Finland , -1, 1 , Neutral, around zero, give or take\n",
+        )
+        .unwrap();
+        let belief = &table.lookup(0)[0];
+        assert_eq!((belief.first, belief.last), (-1, 1));
+        assert_eq!(belief.culture, "Finland");
+        assert_eq!(belief.meaning, "around zero, give or take");
+
+        let error = |text: &str| NumberTable::parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("\nChina,4,4,unlucky"),
+            "line 2: expected culture,first,last,sentiment,meaning"
+        );
+        assert_eq!(
+            error("China,four,4,unlucky,death"),
+            "line 1: `four` is not a whole number"
+        );
+        assert_eq!(error("China,5,4,unlucky,death"), "line 1: 5 comes after 4");
+        assert_eq!(
+            error("China,4,4,scary,death"),
+            "line 1: `scary` should be lucky, unlucky or neutral"
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+use std::mem;
+use std::ops::{Deref, DerefMut, RangeBounds};
+use std::vec::Drain;
+
+// one time the Vec had to move to a new piece of memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reallocation {
+    pub old_capacity: usize,
+    pub new_capacity: usize,
+    // the items that were already there had to be copied over
+    pub bytes_copied: usize,
+}
+
+// what all the reallocations of a TrackedVec add up to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReallocationReport {
+    pub reallocations: usize,
+    pub grows: usize,
+    pub shrinks: usize,
+    pub bytes_copied: usize,
+    pub capacity: usize,
+    // the most items the Vec ever held: `Vec::with_capacity(peak_len)` would never have reallocated
+    pub peak_len: usize,
+}
+
+impl fmt::Display for ReallocationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} reallocations ({} grows, {} shrinks), {} bytes copied, capacity {}, at most {} items",
+            self.reallocations, self.grows, self.shrinks, self.bytes_copied, self.capacity, self.peak_len
+        )
+    }
+}
+
+// TrackedVec : a Vec that writes down every time it reallocates
+// - derefs to a slice, so indexing, iterating, sorting and the rest work like on a Vec
+// - only the methods here can change the capacity, and each one checks it before and after
+#[derive(Debug, Clone, Default)]
+pub struct TrackedVec<T> {
+    items: Vec<T>,
+    reallocations: Vec<Reallocation>,
+    peak_len: usize,
+}
+
+impl<T> TrackedVec<T> {
+    pub fn new() -> Self {
+        Self::from(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(Vec::with_capacity(capacity))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.track(|items| items.push(item));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn insert(&mut self, index: usize, item: T) {
+        self.track(|items| items.insert(index, item));
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.items.remove(index)
+    }
+
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.items.swap_remove(index)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.items.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, keep: F) {
+        self.items.retain(keep);
+    }
+
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        self.items.drain(range)
+    }
+
+    // the items from `at` on are moved into a new Vec, this one keeps its capacity
+    pub fn split_off(&mut self, at: usize) -> Vec<T> {
+        self.track(|items| items.split_off(at))
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.track(|items| items.reserve(additional));
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.track(|items| items.reserve_exact(additional));
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.track(|items| items.shrink_to_fit());
+    }
+
+    pub fn append(&mut self, other: &mut Vec<T>) {
+        self.track(|items| items.append(other));
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+
+    pub fn reallocations(&self) -> &[Reallocation] {
+        &self.reallocations
+    }
+
+    pub fn clear_history(&mut self) {
+        self.reallocations.clear();
+        self.peak_len = self.items.len();
+    }
+
+    pub fn report(&self) -> ReallocationReport {
+        let grows = self
+            .reallocations
+            .iter()
+            .filter(|reallocation| reallocation.new_capacity > reallocation.old_capacity)
+            .count();
+        ReallocationReport {
+            reallocations: self.reallocations.len(),
+            grows,
+            shrinks: self.reallocations.len() - grows,
+            bytes_copied: self
+                .reallocations
+                .iter()
+                .map(|reallocation| reallocation.bytes_copied)
+                .sum(),
+            capacity: self.items.capacity(),
+            peak_len: self.peak_len,
+        }
+    }
+
+    // runs a change on the inner Vec and writes down if it had to reallocate
+    // a Vec grows at most once per call here, so comparing before and after catches every move
+    fn track<R>(&mut self, change: impl FnOnce(&mut Vec<T>) -> R) -> R {
+        let old_capacity = self.items.capacity();
+        let old_len = self.items.len();
+        let result = change(&mut self.items);
+        let new_capacity = self.items.capacity();
+        if new_capacity != old_capacity {
+            self.reallocations.push(Reallocation {
+                old_capacity,
+                new_capacity,
+                bytes_copied: old_len * mem::size_of::<T>(),
+            });
+        }
+        self.peak_len = self.peak_len.max(self.items.len());
+        result
+    }
+}
+
+impl<T: Clone> TrackedVec<T> {
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.track(|items| items.extend_from_slice(other));
+    }
+
+    pub fn resize(&mut self, len: usize, value: T) {
+        self.track(|items| items.resize(len, value));
+    }
+
+    // clones the items in `range` onto the end
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.track(|items| items.extend_from_within(range));
+    }
+}
+
+impl<T: PartialEq> TrackedVec<T> {
+    pub fn dedup(&mut self) {
+        self.items.dedup();
+    }
+}
+
+impl<T> Deref for TrackedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> DerefMut for TrackedVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+}
+
+impl<T> From<Vec<T>> for TrackedVec<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            peak_len: items.len(),
+            items,
+            reallocations: Vec::new(),
+        }
+    }
+}
+
+impl<T> From<TrackedVec<T>> for Vec<T> {
+    fn from(tracked: TrackedVec<T>) -> Self {
+        tracked.items
+    }
+}
+
+// reserves what the iterator says it has first, like Vec::extend does,
+// then pushes one by one so a wrong guess still shows up as its own reallocation
+impl<T> Extend<T> for TrackedVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for TrackedVec<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for TrackedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tracked = Self::new();
+        tracked.extend(iter);
+        tracked
+    }
+}
+
+impl<T> IntoIterator for TrackedVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TrackedVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut TrackedVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter_mut()
+    }
+}
+
+impl<T: PartialEq> PartialEq for TrackedVec<T> {
+    // two TrackedVecs are equal when they hold the same items, however they got there
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Eq> Eq for TrackedVec<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(count: usize) -> TrackedVec<u32> {
+        let mut tracked = TrackedVec::new();
+        for number in 0..count as u32 {
+            tracked.push(number);
+        }
+        tracked
+    }
+
+    #[test]
+    fn growing_one_by_one_reallocates() {
+        let tracked = filled(100);
+        let report = tracked.report();
+        assert!(report.reallocations > 1);
+        assert_eq!(report.grows, report.reallocations);
+        assert_eq!(report.peak_len, 100);
+        let copied = tracked
+            .reallocations()
+            .iter()
+            .map(|reallocation| reallocation.bytes_copied)
+            .sum::<usize>();
+        assert_eq!(report.bytes_copied, copied);
+        assert_eq!(tracked.reallocations()[0].old_capacity, 0);
+    }
+
+    #[test]
+    fn replaying_with_peak_len_never_reallocates() {
+        let mut tracked = filled(100);
+        tracked.truncate(10);
+        tracked.extend_from_within(..);
+        tracked.retain(|number| number % 2 == 0);
+        let mut replay = TrackedVec::with_capacity(tracked.report().peak_len);
+        for number in 0..100 {
+            replay.push(number);
+        }
+        replay.truncate(10);
+        replay.extend_from_within(..);
+        replay.retain(|number| number % 2 == 0);
+        assert_eq!(replay.report().reallocations, 0);
+        assert!(replay.reallocations().is_empty());
+        assert_eq!(replay, tracked);
+    }
+
+    #[test]
+    fn extend_reserves_the_size_hint_once() {
+        let mut tracked = TrackedVec::new();
+        tracked.extend(0..1_000u32);
+        assert_eq!(
+            tracked.reallocations(),
+            [Reallocation {
+                old_capacity: 0,
+                new_capacity: 1_000,
+                bytes_copied: 0
+            }]
+        );
+        tracked.extend(&[1, 2, 3]);
+        assert_eq!(tracked.reallocations().len(), 2);
+        assert_eq!(tracked.reallocations()[1].bytes_copied, 1_000 * 4);
+        assert_eq!(tracked.len(), 1_003);
+    }
+
+    #[test]
+    fn extend_records_every_growth_of_a_filtered_iterator() {
+        // a filter can't tell how many items it will give, so nothing is reserved up front
+        let mut tracked = TrackedVec::new();
+        tracked.extend((0..5_000u32).filter(|number| number % 2 == 0));
+        assert_eq!(tracked.len(), 2_500);
+        let capacities = tracked
+            .reallocations()
+            .iter()
+            .map(|reallocation| reallocation.new_capacity)
+            .collect::<Vec<usize>>();
+        assert_eq!(
+            capacities,
+            [4, 8, 16, 32, 64, 128, 256, 512, 1_024, 2_048, 4_096]
+        );
+        let report = tracked.report();
+        assert_eq!(report.reallocations, 11);
+        // every growth copies what the one before it made room for
+        assert_eq!(
+            report.bytes_copied,
+            (4 + 8 + 16 + 32 + 64 + 128 + 256 + 512 + 1_024 + 2_048) * 4
+        );
+
+        let collected = (0..5_000u32)
+            .filter(|number| number % 2 == 0)
+            .collect::<TrackedVec<u32>>();
+        assert_eq!(collected.report(), report);
+    }
+
+    #[test]
+    fn drain_dedup_and_split_off_keep_the_capacity() {
+        let mut tracked = TrackedVec::from(vec![1, 1, 2, 2, 2, 3, 4, 4]);
+        let capacity = tracked.capacity();
+        tracked.dedup();
+        assert_eq!(*tracked, [1, 2, 3, 4]);
+        assert_eq!(tracked.drain(1..3).collect::<Vec<i32>>(), [2, 3]);
+        assert_eq!(tracked.split_off(1), [4]);
+        assert_eq!(*tracked, [1]);
+        assert_eq!(tracked.capacity(), capacity);
+        assert!(tracked.reallocations().is_empty());
+        assert_eq!(tracked.report().peak_len, 8);
+    }
+
+    #[test]
+    fn shrinking_is_recorded() {
+        let mut tracked = filled(100);
+        tracked.clear_history();
+        tracked.truncate(3);
+        tracked.shrink_to_fit();
+        let report = tracked.report();
+        assert_eq!((report.grows, report.shrinks), (0, 1));
+        assert_eq!(report.capacity, 3);
+        assert_eq!(report.bytes_copied, 3 * mem::size_of::<u32>());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::date::{days_in_month, Date};
+use crate::Season;
+
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+// meteorological seasons start on the first of March, June, September and December
+// astronomical seasons start on the equinoxes and solstices, around the 20th of those months
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonDefinition {
+    Meteorological,
+    Astronomical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Month {
+    pub number: u32,
+    pub name: &'static str,
+    pub first_day: Date,
+    pub last_day: Date,
+}
+
+// a season from its first day to its last day (both included)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeasonSpan {
+    pub season: Season,
+    pub first_day: Date,
+    pub last_day: Date,
+}
+
+pub fn season_of(date: &Date, hemisphere: Hemisphere, definition: SeasonDefinition) -> Season {
+    boundaries_around(date.year(), hemisphere, definition)
+        .into_iter()
+        .rev()
+        .find(|(_, start)| start <= date)
+        .map(|(season, _)| season)
+        .expect("the last season of the year before starts before this year")
+}
+
+pub fn months(year: i32) -> impl Iterator<Item = Month> {
+    (1..=12).map(move |number| Month {
+        number,
+        name: MONTH_NAMES[number as usize - 1],
+        first_day: Date::new(year, number, 1).unwrap(),
+        last_day: Date::new(year, number, days_in_month(year, number)).unwrap(),
+    })
+}
+
+// every season that has at least one day in `year`, in order,
+// so the first and last ones reach into the years before and after
+pub fn seasons(year: i32, hemisphere: Hemisphere, definition: SeasonDefinition) -> Vec<SeasonSpan> {
+    boundaries_around(year, hemisphere, definition)
+        .windows(2)
+        .map(|pair| SeasonSpan {
+            season: pair[0].0,
+            first_day: pair[0].1,
+            last_day: pair[1].1.add_days(-1),
+        })
+        .collect()
+}
+
+// the last season start of the year before, the four of this year, and the first of the next
+fn boundaries_around(
+    year: i32,
+    hemisphere: Hemisphere,
+    definition: SeasonDefinition,
+) -> Vec<(Season, Date)> {
+    let before = season_starts(year - 1, hemisphere, definition)[3];
+    let after = season_starts(year + 1, hemisphere, definition)[0];
+
+    let mut boundaries = vec![before];
+    boundaries.extend(season_starts(year, hemisphere, definition));
+    boundaries.push(after);
+    boundaries
+}
+
+// the seasons that start in March, June, September and December
+fn season_starts(
+    year: i32,
+    hemisphere: Hemisphere,
+    definition: SeasonDefinition,
+) -> [(Season, Date); 4] {
+    use Season::*;
+    let order = match hemisphere {
+        Hemisphere::Northern => [Spring, Summer, Autumn, Winter],
+        Hemisphere::Southern => [Autumn, Winter, Spring, Summer],
+    };
+
+    let mut starts = [(Spring, Date::new(year, 3, 1).unwrap()); 4];
+    for (i, season) in order.into_iter().enumerate() {
+        let start = match definition {
+            SeasonDefinition::Meteorological => Date::new(year, 3 + 3 * i as u32, 1).unwrap(),
+            SeasonDefinition::Astronomical => equinox_or_solstice(year, i),
+        };
+        starts[i] = (season, start);
+    }
+    starts
+}
+
+// the date (UTC) of the March equinox (0), June solstice (1), September equinox (2)
+// or December solstice (3)
+fn equinox_or_solstice(year: i32, which: usize) -> Date {
+    Date::from_days_since_epoch(
+        (equinox_or_solstice_julian_day(year, which) - 2440587.5).floor() as i64,
+    )
+}
+
+// the moment of an equinox or solstice as a Julian day,
+// from Jean Meeus' "Astronomical Algorithms", good for 1000..3000
+// - strictly this is Terrestrial Time, which runs about a minute ahead of UTC nowadays
+fn equinox_or_solstice_julian_day(year: i32, which: usize) -> f64 {
+    // mean dates as Julian days, in powers of y
+    const MEAN: [[f64; 5]; 4] = [
+        [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+        [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+        [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+        [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+    ];
+    // periodic terms: amplitude, phase and speed (degrees)
+    const TERMS: [(f64, f64, f64); 24] = [
+        (485.0, 324.96, 1934.136),
+        (203.0, 337.23, 32964.467),
+        (199.0, 342.08, 20.186),
+        (182.0, 27.85, 445267.112),
+        (156.0, 73.14, 45036.886),
+        (136.0, 171.52, 22518.443),
+        (77.0, 222.54, 65928.934),
+        (74.0, 296.72, 3034.906),
+        (70.0, 243.58, 9037.513),
+        (58.0, 119.81, 33718.147),
+        (52.0, 297.17, 150.678),
+        (50.0, 21.02, 2281.226),
+        (45.0, 247.54, 29929.562),
+        (44.0, 325.15, 31555.956),
+        (29.0, 60.93, 4443.417),
+        (18.0, 155.12, 67555.328),
+        (17.0, 288.79, 4562.452),
+        (16.0, 198.04, 62894.029),
+        (14.0, 199.76, 31436.921),
+        (12.0, 95.39, 14577.848),
+        (12.0, 287.11, 31931.756),
+        (12.0, 320.81, 34777.259),
+        (9.0, 227.73, 1222.114),
+        (8.0, 15.45, 16859.074),
+    ];
+
+    let y = (f64::from(year) - 2000.0) / 1000.0;
+    let mean = MEAN[which]
+        .iter()
+        .rev()
+        .fold(0.0, |total, coefficient| total * y + coefficient);
+
+    let t = (mean - 2451545.0) / 36525.0;
+    let w = (35999.373 * t - 2.47).to_radians();
+    let lambda = 1.0 + 0.0334 * w.cos() + 0.0007 * (2.0 * w).cos();
+    let sum = TERMS
+        .iter()
+        .map(|(a, b, c)| a * (b + c * t).to_radians().cos())
+        .sum::<f64>();
+    mean + 0.00001 * sum / lambda
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTime;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    fn minutes_since_epoch(moment: DateTime) -> f64 {
+        (moment.date().days_since_epoch() * 1440 + i64::from(moment.minutes_since_midnight()))
+            as f64
+    }
+
+    #[test]
+    fn equinoxes_and_solstices_match_published_times() {
+        // (year, which, month, day, hour, minute) in UTC, from the US Naval Observatory
+        let published = [
+            (2024, 0, 3, 20, 3, 6),
+            (2024, 1, 6, 20, 20, 51),
+            (2024, 2, 9, 22, 12, 44),
+            (2024, 3, 12, 21, 9, 20),
+            (2023, 3, 12, 22, 3, 27),
+            (2025, 0, 3, 20, 9, 1),
+            (2000, 1, 6, 21, 1, 48),
+        ];
+        for (year, which, month, day, hour, minute) in published {
+            let expected = DateTime::new(date(year, month, day), hour, minute).unwrap();
+            let julian_day = equinox_or_solstice_julian_day(year, which);
+            let off_by = (julian_day - 2440587.5) * 1440.0 - minutes_since_epoch(expected);
+            // Terrestrial Time is a little over a minute ahead of UTC
+            assert!(off_by.abs() < 3.0, "{expected}: off by {off_by} minutes");
+            assert_eq!(equinox_or_solstice(year, which), expected.date());
+        }
+    }
+
+    #[test]
+    fn astronomical_seasons() {
+        let seasons = seasons(2024, Hemisphere::Northern, SeasonDefinition::Astronomical);
+        let starts = seasons
+            .iter()
+            .map(|span| (span.season, span.first_day))
+            .collect::<Vec<(Season, Date)>>();
+        assert_eq!(
+            starts,
+            [
+                (Season::Winter, date(2023, 12, 22)),
+                (Season::Spring, date(2024, 3, 20)),
+                (Season::Summer, date(2024, 6, 20)),
+                (Season::Autumn, date(2024, 9, 22)),
+                (Season::Winter, date(2024, 12, 21)),
+            ]
+        );
+        assert_eq!(seasons[0].last_day, date(2024, 3, 19));
+        assert_eq!(seasons[4].last_day, date(2025, 3, 19));
+    }
+
+    #[test]
+    fn season_of_a_date() {
+        use SeasonDefinition::*;
+        let north = |date: Date, definition| season_of(&date, Hemisphere::Northern, definition);
+        let south = |date: Date, definition| season_of(&date, Hemisphere::Southern, definition);
+        assert_eq!(north(date(2024, 3, 19), Astronomical), Season::Winter);
+        assert_eq!(north(date(2024, 3, 20), Astronomical), Season::Spring);
+        assert_eq!(north(date(2024, 3, 1), Meteorological), Season::Spring);
+        assert_eq!(north(date(2024, 2, 29), Meteorological), Season::Winter);
+        assert_eq!(north(date(2024, 1, 1), Astronomical), Season::Winter);
+        assert_eq!(south(date(2024, 1, 1), Meteorological), Season::Summer);
+        assert_eq!(south(date(2024, 7, 1), Astronomical), Season::Winter);
+    }
+
+    #[test]
+    fn meteorological_seasons_end_on_the_last_day_of_a_month() {
+        let seasons = seasons(2024, Hemisphere::Southern, SeasonDefinition::Meteorological);
+        assert_eq!(seasons.len(), 5);
+        assert_eq!(seasons[0].season, Season::Summer);
+        assert_eq!(
+            (seasons[0].first_day, seasons[0].last_day),
+            (date(2023, 12, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            (seasons[2].first_day, seasons[2].last_day),
+            (date(2024, 6, 1), date(2024, 8, 31))
+        );
+    }
+
+    #[test]
+    fn months_of_a_year() {
+        let months = months(2024).collect::<Vec<Month>>();
+        assert_eq!(months.len(), 12);
+        assert_eq!(months[1].name, "February");
+        assert_eq!(months[1].last_day, date(2024, 2, 29));
+        assert_eq!(months[11].last_day, date(2024, 12, 31));
+        assert_eq!(
+            super::months(2023).nth(1).unwrap().last_day,
+            date(2023, 2, 28)
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Climate;
+
+// the coldest month has to be above this (°C) for a climate to count as temperate
+// Köppen first used -3 °C, most maps today use 0 °C
+const TEMPERATE_COLDEST_MONTH: f64 = 0.0;
+
+// monthly averages for January..December
+// - temperature in °C
+// - precipitation in mm
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClimateData {
+    temperature: [f64; 12],
+    precipitation: [f64; 12],
+}
+
+impl ClimateData {
+    pub fn new(temperature: [f64; 12], precipitation: [f64; 12]) -> Result<Self, ClimateDataError> {
+        for month in 0..12 {
+            if !temperature[month].is_finite() || !precipitation[month].is_finite() {
+                return Err(ClimateDataError::NotANumber { month: month + 1 });
+            }
+            if precipitation[month] < 0.0 {
+                return Err(ClimateDataError::NegativePrecipitation {
+                    month: month + 1,
+                    value: precipitation[month],
+                });
+            }
+        }
+        Ok(Self {
+            temperature,
+            precipitation,
+        })
+    }
+
+    pub fn mean_temperature(&self) -> f64 {
+        self.temperature.iter().sum::<f64>() / 12.0
+    }
+
+    pub fn annual_precipitation(&self) -> f64 {
+        self.precipitation.iter().sum()
+    }
+
+    pub fn warmest_month(&self) -> f64 {
+        self.temperature.iter().copied().fold(f64::MIN, f64::max)
+    }
+
+    pub fn coldest_month(&self) -> f64 {
+        self.temperature.iter().copied().fold(f64::MAX, f64::min)
+    }
+
+    // Köppen main groups, checked in the usual order:
+    // E (Polar) -> B (Dry) -> A (Tropical) -> C (Temperate) -> D (Continental)
+    pub fn classify(&self) -> Climate {
+        if self.warmest_month() < 10.0 {
+            Climate::Polar
+        } else if self.annual_precipitation() < self.dry_threshold() {
+            Climate::Dry
+        } else if self.coldest_month() >= 18.0 {
+            Climate::Tropical
+        } else if self.coldest_month() > TEMPERATE_COLDEST_MONTH {
+            Climate::Temperate
+        } else {
+            Climate::Continental
+        }
+    }
+
+    // below this much rain (mm per year) a place is dry,
+    // more rain is needed when it falls in summer because more of it evaporates
+    fn dry_threshold(&self) -> f64 {
+        let total = self.annual_precipitation();
+        let summer_share = if total > 0.0 {
+            self.summer_precipitation() / total
+        } else {
+            0.5
+        };
+        let base = 20.0 * self.mean_temperature();
+        let threshold = match summer_share {
+            share if share >= 0.7 => base + 280.0,
+            share if share <= 0.3 => base,
+            _ => base + 140.0,
+        };
+        threshold.max(0.0)
+    }
+
+    // summer is April..September in the north and October..March in the south,
+    // so the warmer half of the year is taken as summer
+    fn summer_precipitation(&self) -> f64 {
+        let april_to_september = 3..9;
+        let warm_months = self.temperature[april_to_september.clone()]
+            .iter()
+            .sum::<f64>();
+        let northern = warm_months >= self.temperature.iter().sum::<f64>() - warm_months;
+
+        self.precipitation
+            .iter()
+            .enumerate()
+            .filter(|(month, _)| april_to_september.contains(month) == northern)
+            .map(|(_, mm)| mm)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+pub enum ClimateDataError {
+    NotANumber { month: usize },
+    NegativePrecipitation { month: usize, value: f64 },
+}
+
+impl fmt::Display for ClimateDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClimateDataError::NotANumber { month } => {
+                write!(f, "month {month} has a value that is not a number")
+            }
+            ClimateDataError::NegativePrecipitation { month, value } => {
+                write!(f, "month {month} has negative precipitation: {value} mm")
+            }
+        }
+    }
+}
+
+impl Error for ClimateDataError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Elista, Kalmykia: a steppe right on the line between dry and continental
+    fn elista() -> ClimateData {
+        ClimateData::new(
+            [
+                -4.3, -3.9, 1.6, 10.3, 17.0, 22.2, 25.2, 24.1, 17.6, 10.0, 3.0, -1.9,
+            ],
+            [
+                23.0, 20.0, 24.0, 27.0, 37.0, 46.0, 37.0, 27.0, 28.0, 25.0, 29.0, 27.0,
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tropical_singapore() {
+        let singapore = ClimateData::new(
+            [
+                26.5, 27.1, 27.5, 27.9, 28.3, 28.3, 27.9, 27.9, 27.6, 27.6, 27.0, 26.4,
+            ],
+            [
+                234.0, 114.0, 173.0, 141.0, 159.0, 132.0, 154.0, 171.0, 166.0, 194.0, 256.0, 287.0,
+            ],
+        )
+        .unwrap();
+        assert_eq!(singapore.classify(), Climate::Tropical);
+    }
+
+    #[test]
+    fn desert_cairo() {
+        let cairo = ClimateData::new(
+            [
+                14.0, 15.1, 17.6, 21.2, 24.6, 27.0, 27.6, 27.5, 26.0, 23.4, 19.1, 15.4,
+            ],
+            [5.0, 3.8, 3.8, 1.1, 0.5, 0.1, 0.0, 0.0, 0.0, 0.7, 3.8, 5.9],
+        )
+        .unwrap();
+        assert_eq!(cairo.classify(), Climate::Dry);
+    }
+
+    #[test]
+    fn temperate_melbourne_has_summer_from_october() {
+        let melbourne = ClimateData::new(
+            [
+                21.2, 21.4, 19.3, 16.4, 13.6, 11.2, 10.6, 11.6, 13.5, 15.7, 17.8, 19.8,
+            ],
+            [
+                47.0, 48.0, 50.0, 57.0, 56.0, 49.0, 47.0, 50.0, 58.0, 64.0, 60.0, 59.0,
+            ],
+        )
+        .unwrap();
+        assert_eq!(melbourne.classify(), Climate::Temperate);
+        // October..March
+        assert_eq!(melbourne.summer_precipitation(), 328.0);
+    }
+
+    #[test]
+    fn polar_utqiagvik() {
+        let utqiagvik = ClimateData::new(
+            [
+                -25.6, -27.3, -25.9, -17.5, -6.1, 1.9, 5.0, 3.4, -0.6, -9.6, -18.3, -23.5,
+            ],
+            [
+                4.0, 3.0, 3.0, 3.0, 3.0, 8.0, 25.0, 28.0, 17.0, 10.0, 5.0, 4.0,
+            ],
+        )
+        .unwrap();
+        assert_eq!(utqiagvik.classify(), Climate::Polar);
+    }
+
+    #[test]
+    fn elista_is_just_wet_enough_to_be_continental() {
+        let elista = elista();
+        // 58% of the 350 mm falls April..September, so the threshold is 20 * 10.1 °C + 140 = 342 mm
+        assert!(
+            (elista.dry_threshold() - 341.5).abs() < 0.01,
+            "{}",
+            elista.dry_threshold()
+        );
+        assert_eq!(elista.annual_precipitation(), 350.0);
+        assert_eq!(elista.classify(), Climate::Continental);
+
+        let drier =
+            ClimateData::new(elista.temperature, elista.precipitation.map(|mm| mm * 0.9)).unwrap();
+        assert_eq!(drier.classify(), Climate::Dry);
+    }
+
+    #[test]
+    fn rejects_bad_data() {
+        let mut precipitation = [10.0; 12];
+        precipitation[4] = -1.0;
+        assert!(matches!(
+            ClimateData::new([10.0; 12], precipitation),
+            Err(ClimateDataError::NegativePrecipitation { month: 5, .. })
+        ));
+        let mut temperature = [10.0; 12];
+        temperature[11] = f64::NAN;
+        assert!(matches!(
+            ClimateData::new(temperature, [10.0; 12]),
+            Err(ClimateDataError::NotANumber { month: 12 })
+        ));
+    }
+}
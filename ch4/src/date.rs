@@ -0,0 +1,219 @@
+use std::fmt;
+
+// a day in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    // None if the month or day doesn't exist, like 2023-02-29
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days_since_epoch(self.days_since_epoch() + days)
+    }
+
+    // days since 1970-01-01, negative before that
+    // (Howard Hinnant's `days_from_civil`)
+    pub fn days_since_epoch(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    // the opposite of `days_since_epoch` (Howard Hinnant's `civil_from_days`)
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    // 0 for Monday .. 6 for Sunday
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days_since_epoch() + 3).rem_euclid(7) as u32
+    }
+
+    // the Monday of this date's week
+    pub fn start_of_week(&self) -> Date {
+        Date::from_days_since_epoch(self.days_since_epoch() - i64::from(self.weekday()))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// a moment in UTC, to the minute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    date: Date,
+    hour: u32,
+    minute: u32,
+}
+
+impl DateTime {
+    pub fn new(date: Date, hour: u32, minute: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 {
+            Some(Self { date, hour, minute })
+        } else {
+            None
+        }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn minutes_since_midnight(&self) -> u32 {
+        self.hour * 60 + self.minute
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.date, self.hour, self.minute)
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert!(is_leap_year(-4));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2023, 4), 30);
+        assert_eq!(days_in_month(2023, 12), 31);
+    }
+
+    #[test]
+    fn only_real_dates() {
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(2024, 4, 31).is_none());
+        assert!(Date::new(2024, 0, 1).is_none());
+        assert!(Date::new(2024, 13, 1).is_none());
+        assert!(Date::new(2024, 1, 0).is_none());
+        assert!(DateTime::new(date(2024, 1, 1), 24, 0).is_none());
+        assert!(DateTime::new(date(2024, 1, 1), 23, 60).is_none());
+        assert_eq!(
+            DateTime::new(date(2024, 1, 1), 23, 59)
+                .unwrap()
+                .minutes_since_midnight(),
+            1439
+        );
+    }
+
+    #[test]
+    fn days_since_epoch() {
+        assert_eq!(date(1970, 1, 1).days_since_epoch(), 0);
+        assert_eq!(date(1969, 12, 31).days_since_epoch(), -1);
+        assert_eq!(date(2000, 3, 1).days_since_epoch(), 11_017);
+        assert_eq!(date(2024, 3, 20).days_since_epoch(), 19_802);
+        for days in [
+            -800_000, -1, 0, 59, 10_956, 11_016, 11_017, 19_802, 1_000_000,
+        ] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+    }
+
+    #[test]
+    fn adding_days_crosses_months_and_years() {
+        assert_eq!(date(2024, 1, 31).add_days(1), date(2024, 2, 1));
+        assert_eq!(date(2024, 2, 28).add_days(1), date(2024, 2, 29));
+        assert_eq!(date(2023, 2, 28).add_days(1), date(2023, 3, 1));
+        assert_eq!(date(1900, 2, 28).add_days(1), date(1900, 3, 1));
+        assert_eq!(date(2000, 2, 28).add_days(1), date(2000, 2, 29));
+        assert_eq!(date(2023, 12, 31).add_days(1), date(2024, 1, 1));
+        assert_eq!(date(2024, 1, 1).add_days(-1), date(2023, 12, 31));
+        assert_eq!(date(2024, 3, 1).add_days(-1), date(2024, 2, 29));
+        assert_eq!(date(2024, 1, 1).add_days(366), date(2025, 1, 1));
+        assert_eq!(date(2023, 1, 1).add_days(365), date(2024, 1, 1));
+        assert_eq!(date(2024, 4, 30).add_days(0), date(2024, 4, 30));
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        assert_eq!(date(1970, 1, 1).weekday(), 3);
+        assert_eq!(date(2024, 2, 29).weekday(), 3);
+        assert_eq!(date(1969, 12, 29).weekday(), 0);
+        assert_eq!(date(2024, 3, 3).start_of_week(), date(2024, 2, 26));
+        assert_eq!(date(2025, 1, 1).start_of_week(), date(2024, 12, 30));
+        assert_eq!(date(2024, 3, 4).start_of_week(), date(2024, 3, 4));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(date(2024, 3, 5).to_string(), "2024-03-05");
+        assert_eq!(
+            DateTime::new(date(987, 12, 31), 3, 6).unwrap().to_string(),
+            "0987-12-31 03:06"
+        );
+        assert!(date(2023, 12, 31) < date(2024, 1, 1));
+    }
+}
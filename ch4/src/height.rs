@@ -0,0 +1,278 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// the shortest and tallest heights we accept, in millimetres
+pub const MIN_HEIGHT: u32 = 300;
+pub const MAX_HEIGHT: u32 = 2_800;
+
+const MILLIMETRES_PER_INCH: f64 = 25.4;
+// `''` is turned into `"` before these are looked for
+const INCH_MARKS: [&str; 4] = ["inches", "inch", "in", "\""];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthUnit {
+    Centimetres,
+    Metres,
+    FeetAndInches,
+}
+
+// Height : how tall a person is, stored in whole millimetres
+// - can be made from and shown in cm, m or feet and inches
+// - always between MIN_HEIGHT and MAX_HEIGHT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Height {
+    millimetres: u32,
+}
+
+impl Height {
+    pub fn from_millimetres(millimetres: f64) -> Result<Self, HeightError> {
+        if !millimetres.is_finite() {
+            return Err(HeightError::OutOfRange(millimetres));
+        }
+        let rounded = millimetres.round();
+        if rounded < f64::from(MIN_HEIGHT) || rounded > f64::from(MAX_HEIGHT) {
+            return Err(HeightError::OutOfRange(millimetres));
+        }
+        Ok(Self {
+            millimetres: rounded as u32,
+        })
+    }
+
+    pub fn from_cm(cm: f64) -> Result<Self, HeightError> {
+        Self::from_millimetres(cm * 10.0)
+    }
+
+    pub fn from_metres(metres: f64) -> Result<Self, HeightError> {
+        Self::from_millimetres(metres * 1000.0)
+    }
+
+    pub fn from_feet_and_inches(feet: u32, inches: f64) -> Result<Self, HeightError> {
+        Self::from_millimetres((f64::from(feet) * 12.0 + inches) * MILLIMETRES_PER_INCH)
+    }
+
+    pub fn cm(&self) -> f64 {
+        f64::from(self.millimetres) / 10.0
+    }
+
+    pub fn metres(&self) -> f64 {
+        f64::from(self.millimetres) / 1000.0
+    }
+
+    // rounded to the nearest inch, so 5 feet 11.6 inches is 6'0"
+    pub fn feet_and_inches(&self) -> (u32, u32) {
+        let inches = (f64::from(self.millimetres) / MILLIMETRES_PER_INCH).round() as u32;
+        (inches / 12, inches % 12)
+    }
+
+    pub fn display_in(&self, unit: LengthUnit) -> String {
+        match unit {
+            LengthUnit::Centimetres => format!("{} cm", self.cm()),
+            LengthUnit::Metres => format!("{:.2} m", self.metres()),
+            LengthUnit::FeetAndInches => {
+                let (feet, inches) = self.feet_and_inches();
+                format!("{feet}'{inches}\"")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_in(LengthUnit::Centimetres))
+    }
+}
+
+// understands the ways people write their height on a form:
+// "170", "170cm", "170 cm", "1.70 m", "1,70m", "5'7\"", "5' 7''", "5ft 7in", "5 feet", "67 in", "67''"
+// a number without a unit is taken as centimetres
+impl FromStr for Height {
+    type Err = HeightError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || HeightError::Invalid(input.to_string());
+        // two single quotes are an inch mark, not two feet marks
+        let text = input
+            .trim()
+            .to_lowercase()
+            .replace(',', ".")
+            .replace("''", "\"");
+        let number = |text: &str| text.trim().parse::<f64>().map_err(|_| invalid());
+
+        // feet and inches: split at the feet mark, whatever is left is inches
+        for feet_mark in ["feet", "foot", "ft", "'"] {
+            let Some((feet, rest)) = text.split_once(feet_mark) else {
+                continue;
+            };
+            let feet = number(feet)?;
+            if feet < 0.0 || feet.fract() != 0.0 {
+                return Err(invalid());
+            }
+            let inches = strip_unit(rest, &INCH_MARKS);
+            let inches = if inches.trim().is_empty() {
+                0.0
+            } else {
+                number(inches)?
+            };
+            if !(0.0..12.0).contains(&inches) {
+                return Err(invalid());
+            }
+            return Height::from_feet_and_inches(feet as u32, inches);
+        }
+
+        if let Some(inches) = INCH_MARKS.iter().find_map(|mark| text.strip_suffix(mark)) {
+            return Height::from_millimetres(number(inches)? * MILLIMETRES_PER_INCH);
+        }
+        if let Some(cm) = text.strip_suffix("cm") {
+            return Height::from_cm(number(cm)?);
+        }
+        if let Some(mm) = text.strip_suffix("mm") {
+            return Height::from_millimetres(number(mm)?);
+        }
+        if let Some(metres) = text.strip_suffix('m') {
+            return Height::from_metres(number(metres)?);
+        }
+        Height::from_cm(number(&text)?)
+    }
+}
+
+fn strip_unit<'a>(text: &'a str, units: &[&str]) -> &'a str {
+    let text = text.trim();
+    units
+        .iter()
+        .find_map(|unit| text.strip_suffix(unit))
+        .unwrap_or(text)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeightError {
+    Invalid(String),
+    // the height in millimetres that was too short or too tall
+    OutOfRange(f64),
+}
+
+impl fmt::Display for HeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeightError::Invalid(input) => write!(f, "{input:?} is not a height"),
+            HeightError::OutOfRange(millimetres) => write!(
+                f,
+                "{} cm is not a human height, expected {} to {} cm",
+                millimetres / 10.0,
+                MIN_HEIGHT / 10,
+                MAX_HEIGHT / 10,
+            ),
+        }
+    }
+}
+
+impl Error for HeightError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Height, HeightError> {
+        input.parse()
+    }
+
+    fn mm(input: &str) -> u32 {
+        parse(input).unwrap().millimetres
+    }
+
+    #[test]
+    fn parses_metric() {
+        for input in [
+            "170", "170cm", "170 cm", " 170 CM ", "1.70 m", "1,70m", "1700 mm", "170.0",
+        ] {
+            assert_eq!(mm(input), 1700, "{input}");
+        }
+        assert_eq!(mm("170.04"), 1700);
+        assert_eq!(mm("170.05"), 1701);
+    }
+
+    #[test]
+    fn parses_feet_and_inches() {
+        for input in [
+            "5'7\"",
+            "5' 7''",
+            "5'7''",
+            "5ft 7in",
+            "5 ft 7 inches",
+            "5 feet 7 inch",
+            "67 in",
+            "67\"",
+            "67''",
+            "67 inches",
+        ] {
+            assert_eq!(mm(input), 1702, "{input}");
+        }
+        assert_eq!(mm("6'"), 1829);
+        assert_eq!(mm("5 feet"), 1524);
+        assert_eq!(mm("5'7.5\""), 1715);
+        assert_eq!(parse("5'7\"").unwrap().feet_and_inches(), (5, 7));
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_height() {
+        for input in [
+            "", "tall", "5'12\"", "5'-1\"", "5.5'", "-5'", "5'7'", "5''7", "cm", "1.70 km",
+        ] {
+            assert_eq!(
+                parse(input),
+                Err(HeightError::Invalid(input.to_string())),
+                "{input}"
+            );
+        }
+        assert_eq!(parse("29").unwrap_err(), HeightError::OutOfRange(290.0));
+        assert_eq!(
+            parse("3 m").unwrap_err().to_string(),
+            "300 cm is not a human height, expected 30 to 280 cm"
+        );
+        assert!(parse("NaN").is_err());
+        assert!(Height::from_cm(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn rounds_to_whole_millimetres() {
+        assert_eq!(Height::from_millimetres(299.5).unwrap().millimetres, 300);
+        assert!(Height::from_millimetres(299.4).is_err());
+        assert_eq!(Height::from_millimetres(2800.4).unwrap().millimetres, 2800);
+        assert!(Height::from_millimetres(2800.5).is_err());
+        // 5 feet 11.6 inches is closer to 6 feet
+        assert_eq!(
+            Height::from_feet_and_inches(5, 11.6)
+                .unwrap()
+                .feet_and_inches(),
+            (6, 0)
+        );
+        assert_eq!(
+            Height::from_feet_and_inches(5, 11.4)
+                .unwrap()
+                .feet_and_inches(),
+            (5, 11)
+        );
+    }
+
+    #[test]
+    fn round_trips_through_every_unit() {
+        for millimetres in (MIN_HEIGHT..=MAX_HEIGHT).step_by(7) {
+            let height = Height::from_millimetres(f64::from(millimetres)).unwrap();
+            assert_eq!(
+                parse(&height.display_in(LengthUnit::Centimetres)),
+                Ok(height)
+            );
+            assert_eq!(Height::from_metres(height.metres()), Ok(height));
+            // feet and inches are rounded to the inch, so they can only come back within half an inch
+            let back = parse(&height.display_in(LengthUnit::FeetAndInches)).unwrap();
+            assert!(
+                back.millimetres.abs_diff(millimetres) <= 13,
+                "{height} came back as {back}"
+            );
+        }
+        let height = parse("5'7\"").unwrap();
+        assert_eq!(height.display_in(LengthUnit::FeetAndInches), "5'7\"");
+        assert_eq!(height.display_in(LengthUnit::Metres), "1.70 m");
+        assert_eq!(height.to_string(), "170.2 cm");
+    }
+}
@@ -1,5 +1,27 @@
 use std::mem::size_of_val;
 
+use calendar::{Hemisphere, SeasonDefinition};
+use climate::ClimateData;
+use date::{Date, DateTime};
+use height::{Height, LengthUnit};
+use mood_journal::MoodJournal;
+use mood_scale::MoodScale;
+use number::{Kind, Number};
+use registry::Registry;
+use sky::{Daylight, Observer};
+use stellar::StellarEvolution;
+
+mod calendar;
+mod climate;
+mod date;
+mod height;
+mod mood_journal;
+mod mood_scale;
+mod number;
+mod registry;
+mod sky;
+mod stellar;
+
 fn main() {
     // 3 types of structs
     // 1. unit struct
@@ -41,29 +63,137 @@ fn main() {
     // when you use a method, Rust will dereference for you until it reaches the original type
     // when you use the dot operator, you don’t need to worry about *
 
-
     // example 1
     let population = 500_000;
     let capital = String::from("Elista");
     let leader_name = String::from("Batu Khasikov");
+    // monthly averages for Elista, the climate is worked out from them
+    let climate_data = ClimateData::new(
+        [
+            -4.3, -3.9, 1.6, 10.3, 17.0, 22.2, 25.2, 24.1, 17.6, 10.0, 3.0, -1.9,
+        ],
+        [
+            23.0, 20.0, 24.0, 27.0, 37.0, 46.0, 37.0, 27.0, 28.0, 25.0, 29.0, 27.0,
+        ],
+    )
+    .expect("Elista's climate data should be valid");
     let kalmykia = Country {
-        population, // population: population,
-        capital, // capital: capital,
+        population,  // population: population,
+        capital,     // capital: capital,
         leader_name, // leader_name: leader_name,
-        climate: Climate::Continental,
+        climate_data,
     }; // if the variable name equals to field name, then use it directly
-
+    println!("Kalmykia has a {:?} climate", kalmykia.climate());
 
     // example 2
-    let time = 8;
-    let skystate = create_skystate(time);
-    check_skystate(&skystate);
+    // the sky depends on where you are and when, not only on the hour
+    let tallinn = Observer {
+        latitude: 59.437,
+        longitude: 24.754,
+    };
+    let midsummer = Date::new(2024, 6, 21).unwrap();
+    if let Daylight::RiseAndSet { sunrise, sunset } = sky::daylight(&tallinn, &midsummer) {
+        println!(
+            "On {midsummer} the sun rises at {} and sets at {} UTC in Tallinn",
+            sky::format_hours(sunrise),
+            sky::format_hours(sunset),
+        );
+    }
+    for hour in [0, 2, 8, 20, 21] {
+        let time = DateTime::new(midsummer, hour, 0).unwrap();
+        let skystate = create_skystate(&tallinn, &time);
+        check_skystate(&skystate);
+    }
 
     // example 3
     let my_mood = Mood::Happy;
     let happiness_level = match_mood(&my_mood);
     println!("Out of 1 to 10, my happiness is {happiness_level}");
 
+    // a journal of moods over a few days
+    let mut journal = MoodJournal::new();
+    let at = |day, hour| DateTime::new(Date::new(2024, 6, day).unwrap(), hour, 0).unwrap();
+    let entries = [
+        (at(3, 8), Mood::Sleepy, "early meeting"),
+        (at(3, 20), Mood::Angry, "train was late, again"),
+        (at(4, 9), Mood::NotBad, ""),
+        (at(5, 9), Mood::Happy, "finished the \"big\" report"),
+        (at(4, 21), Mood::NotBad, "quiet evening"),
+        (at(10, 7), Mood::Happy, "holiday!"),
+    ];
+    for (time, mood, note) in entries {
+        journal
+            .record(time, mood.key(), note)
+            .expect("the built-in scale has every Mood");
+    }
+    // the built-in scale only knows the four moods of `Mood`
+    if let Err(e) = journal.record(at(11, 8), "anxious", "") {
+        println!("{e}");
+    }
+    for (day, average) in journal.daily_averages() {
+        println!("{day}: {average:.1}");
+    }
+    for (week, average) in journal.weekly_averages() {
+        println!("week of {week}: {average:.1}");
+    }
+    if let Some(streak) = journal.longest_streak() {
+        println!(
+            "Longest streak: {} {} times from {} to {}",
+            streak.mood, streak.length, streak.from, streak.to
+        );
+    }
+    println!(
+        "Current streak: {:?}",
+        journal.current_streak().map(|streak| streak.length)
+    );
+    println!("My mood is {:?}", journal.trend());
+    print!("{}", journal.to_csv());
+    // `cargo run -- moods.csv` saves the journal as well
+    if let Some(csv_path) = std::env::args().nth(1) {
+        match journal.export_csv(&csv_path) {
+            Ok(()) => println!("Saved {} entries to {csv_path}", journal.entries().len()),
+            Err(e) => println!("Could not save the journal: {e}"),
+        }
+    }
+
+    // teams can score moods their own way with a mood scale config
+    let scale_path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/moods.ini");
+    match MoodScale::from_config_file(scale_path) {
+        Ok(scale) => {
+            for mood in scale.moods() {
+                println!(
+                    "{} ({}) is worth {} and shown in {}",
+                    mood.name,
+                    scale.label(&mood.name, "ko").unwrap_or_default(),
+                    mood.score,
+                    mood.colour.as_deref().unwrap_or("no colour"),
+                );
+            }
+            println!("angry in German is {:?}", scale.label("Angry", "de"));
+            println!("anxious scores {:?}", scale.score("anxious"));
+            let mut team_journal = MoodJournal::with_scale(scale);
+            for entry in journal.entries() {
+                team_journal
+                    .record(entry.at, &entry.mood, &entry.note)
+                    .expect("the team's scale has every Mood");
+            }
+            // moods that only the team's scale has are scored like any other
+            match team_journal.record(at(4, 13), "Anxious", "deadline moved up") {
+                Ok(()) => println!("Recorded {} team entries", team_journal.entries().len()),
+                Err(e) => println!("{e}"),
+            }
+            for (day, average) in team_journal.daily_averages() {
+                println!("{day} on the team's scale: {average:.1}");
+            }
+        }
+        Err(e) => println!("Could not load {scale_path}: {e}"),
+    }
+    // a scale that breaks the rules is rejected
+    let bad_scale = "min = 0\nmax = 5\n[happy]\nscore = 10\n";
+    if let Err(e) = MoodScale::from_config_str(bad_scale) {
+        println!("{e}");
+    }
+
     // example 4 : use
     let size_of_jaurim = size_of_val("Shou");
     println!("{size_of_jaurim}");
@@ -75,6 +205,35 @@ fn main() {
         println!("{}", season as u32);
     }
 
+    // the season of a date depends on the hemisphere and on who you ask
+    let date = Date::new(2024, 3, 10).unwrap();
+    for hemisphere in [Hemisphere::Northern, Hemisphere::Southern] {
+        for definition in [
+            SeasonDefinition::Meteorological,
+            SeasonDefinition::Astronomical,
+        ] {
+            let season = calendar::season_of(&date, hemisphere, definition);
+            println!("{date} is in {season:?} ({hemisphere:?}, {definition:?})");
+        }
+    }
+    for span in calendar::seasons(2024, Hemisphere::Southern, SeasonDefinition::Astronomical) {
+        println!("{:?}: {} to {}", span.season, span.first_day, span.last_day);
+    }
+    for month in calendar::months(2024) {
+        let season = calendar::season_of(
+            &month.first_day,
+            Hemisphere::Northern,
+            SeasonDefinition::Meteorological,
+        );
+        println!(
+            "{} {} ({} days, {:?})",
+            month.number,
+            month.name,
+            month.last_day.day(),
+            season
+        );
+    }
+
     // example 6 : assigned value
     use Star::*;
     let five_stars = vec![BrownDwarf, RedDwarf, YellowStar, RedGiant, DeadStar];
@@ -82,15 +241,80 @@ fn main() {
         println!("{}", star as u32);
     }
 
+    // how a star changes over its life depends on its mass
+    for mass in [0.05, 0.2, 1.0, 25.0, 500.0] {
+        match StellarEvolution::new(mass) {
+            Ok(evolution) => print!("{}", evolution.timeline()),
+            Err(e) => println!("{e}"),
+        }
+    }
+    let sun = StellarEvolution::new(1.0).unwrap();
+    for (step, until) in [(2.5e9, 1.5e10), (1.0, 1.5e10)] {
+        match sun.simulate(step, until) {
+            Ok(stages) => {
+                for (age, star) in stages {
+                    println!(
+                        "After {} a star like the sun is a {star:?}",
+                        stellar::format_years(age)
+                    );
+                }
+            }
+            Err(e) => println!("{e}"),
+        }
+    }
+    println!(
+        "The sun spends {} as a {:?}",
+        stellar::format_years(stellar::main_sequence_lifetime(sun.mass())),
+        sun.phases()[0].stage,
+    );
+
     // example 7 : put enum in vec
     let my_vec = vec![get_number(-800), get_number(8)];
     for item in my_vec {
         match item {
             Number::U32(number) => println!("A u32 with the value {number}"),
             Number::I32(number) => println!("An i32 with the value {number}"),
+            other => println!("A {} with the value {other}", other.kind()),
         }
     }
 
+    // mixed numbers are promoted before the math, and overflow is an error
+    let parsed = [
+        "200u8",
+        "-5",
+        "3000000000",
+        "2.5f32",
+        "1_000i16",
+        "1e3",
+        "300u8",
+        "lots",
+    ]
+    .iter()
+    .map(|input| input.parse::<Number>())
+    .collect::<Vec<_>>();
+    for number in &parsed {
+        match number {
+            Ok(number) => println!("Parsed {number:#}"),
+            Err(e) => println!("{e}"),
+        }
+    }
+    let (big, small) = (Number::U8(200), Number::I8(-5));
+    println!(
+        "{big:#} + {small:#} = {:?}",
+        big.checked_add(small).map(|n| format!("{n:#}"))
+    );
+    println!(
+        "{big:#} + {big:#} = {:?}",
+        big.checked_add(big).map_err(|e| e.to_string())
+    );
+    println!("{big:#} * 2.5 = {:?}", big.checked_mul(Number::F64(2.5)));
+    println!(
+        "{big:#} / 0 = {:?}",
+        big.checked_div(Number::U8(0)).map_err(|e| e.to_string())
+    );
+    println!("{:?}", Number::I64(i64::MAX).checked_sub(Number::I32(-1)));
+    println!("{:?}", Number::F64(0.5).convert_to(Kind::I32));
+
     // associated functions
     let mut my_string = String::from("I feel excited");
     my_string.push('!');
@@ -107,19 +331,42 @@ fn main() {
     let papa_doc = Person {
         name: "Papa Doc".to_string(),
         real_name: "Clarence".to_string(),
-        height: 170,
+        height: Height::from_cm(170.0).unwrap(),
         happiness: false,
     };
 
     let Person {
         name: fake_name,
         real_name,
-        height: cm,
+        height,
         happiness,
     } = papa_doc;
 
-    println!("they call him {fake_name} but his real name is {real_name}.
-    He is {cm} cm tall and is he happy? {happiness}");
+    println!(
+        "they call him {fake_name} but his real name is {real_name}.
+    He is {height} tall and is he happy? {happiness}"
+    );
+
+    // heights can be written in many ways, and must be a height a person could have
+    for input in [
+        "170",
+        "1,70 m",
+        "5'7\"",
+        "5 ft 11 in",
+        "6 feet",
+        "72 inches",
+        "300 cm",
+        "tall",
+    ] {
+        match input.parse::<Height>() {
+            Ok(height) => println!(
+                "{input} is {height}, {} or {}",
+                height.display_in(LengthUnit::Metres),
+                height.display_in(LengthUnit::FeetAndInches),
+            ),
+            Err(e) => println!("{e}"),
+        }
+    }
 
     // example 10
     let tallinn = City::new("Tallinn", "Reval", 426_538, 1219);
@@ -129,12 +376,45 @@ fn main() {
     let papa_doc = Person {
         name: "Papa Doc".to_string(),
         real_name: "Clarence".to_string(),
-        height: 170,
+        height: "1.70 m".parse().unwrap(),
         happiness: false,
     };
     check_if_happy(&papa_doc);
     check_if_happy_destructured(&papa_doc);
 
+    // a registry knows everyone by all of their names
+    let mut registry = Registry::new();
+    let papa_doc_id = registry.register(papa_doc).unwrap();
+    for alias in ["Doc", "The Doctor", "doc"] {
+        if let Err(e) = registry.add_alias(papa_doc_id, alias) {
+            println!("{e}");
+        }
+    }
+    let impostor = Person {
+        name: "Doc".to_string(),
+        real_name: "Emmett".to_string(),
+        height: Height::from_feet_and_inches(6, 1.0).unwrap(),
+        happiness: true,
+    };
+    if let Err(e) = registry.register(impostor) {
+        println!("Could not register: {e}");
+    }
+    for name in ["the doctor", "PAPA DOC", "Emmett"] {
+        match registry.who_is(name) {
+            Some(real_name) => println!("{name} is really {real_name}"),
+            None => println!("Nobody is called {name}"),
+        }
+    }
+    if let Some(person) = registry.get(papa_doc_id) {
+        println!(
+            "{} goes by {:?}",
+            person.real_name,
+            registry.names_of(papa_doc_id)
+        );
+    }
+    println!("{} people are registered", registry.len());
+    check_if_happy(registry.find("clarence").unwrap());
+
     // example 12
     // dereferencing
     let my_name = "Shou".to_string();
@@ -142,6 +422,7 @@ fn main() {
     println!("{}", &&&&my_name.is_empty());
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Climate {
     Tropical,
     Dry,
@@ -154,17 +435,42 @@ struct Country {
     population: u32,
     capital: String,
     leader_name: String,
-    climate: Climate,
+    climate_data: ClimateData,
+}
+
+impl Country {
+    fn climate(&self) -> Climate {
+        self.climate_data.classify()
+    }
 }
 
 enum ThingsInTheSky {
     Sun(String),
+    CivilTwilight(String),
+    NauticalTwilight(String),
+    AstronomicalTwilight(String),
     Stars(String),
 }
 
-fn create_skystate(time: i32) -> ThingsInTheSky {
-    match time {
-        6..=18 => ThingsInTheSky::Sun(String::from("I can see the sun!")),
+// the twilight phases are named after how far the sun is below the horizon
+fn create_skystate(observer: &Observer, time: &DateTime) -> ThingsInTheSky {
+    match sky::solar_elevation(observer, time) {
+        elevation if elevation > sky::SUNRISE_ELEVATION => {
+            ThingsInTheSky::Sun(String::from("I can see the sun!"))
+        }
+        elevation if elevation > sky::CIVIL_TWILIGHT_ELEVATION => {
+            ThingsInTheSky::CivilTwilight(String::from("The sun is down but it's still bright!"))
+        }
+        elevation if elevation > sky::NAUTICAL_TWILIGHT_ELEVATION => {
+            ThingsInTheSky::NauticalTwilight(String::from(
+                "I can see the horizon and the brightest stars!",
+            ))
+        }
+        elevation if elevation > sky::ASTRONOMICAL_TWILIGHT_ELEVATION => {
+            ThingsInTheSky::AstronomicalTwilight(String::from(
+                "It's dark but the sky isn't black yet!",
+            ))
+        }
         _ => ThingsInTheSky::Stars(String::from("I can see the stars!")),
     }
 }
@@ -172,10 +478,14 @@ fn create_skystate(time: i32) -> ThingsInTheSky {
 fn check_skystate(state: &ThingsInTheSky) {
     match state {
         ThingsInTheSky::Sun(description) => println!("{description}"),
+        ThingsInTheSky::CivilTwilight(description)
+        | ThingsInTheSky::NauticalTwilight(description)
+        | ThingsInTheSky::AstronomicalTwilight(description) => println!("{description}"),
         ThingsInTheSky::Stars(n) => println!("{n}"),
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Mood {
     Happy,
     Sleepy,
@@ -183,17 +493,13 @@ enum Mood {
     Angry,
 }
 
+// the score comes from the built-in mood scale
 fn match_mood(mood: &Mood) -> i32 {
-    use Mood::*;
-    let happiness_level = match mood {
-        Happy => 10,
-        Sleepy => 6,
-        NotBad => 7,
-        Angry => 2,
-    };
+    let (_, happiness_level) = mood_scale::builtin_mood(*mood);
     happiness_level
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Season {
     Spring,
     Summer,
@@ -201,6 +507,7 @@ enum Season {
     Winter,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Star {
     BrownDwarf,
     RedDwarf = 50,
@@ -209,17 +516,13 @@ enum Star {
     DeadStar,
 }
 
-enum Number {
-    U32(u32),
-    I32(i32),
-}
-
+// positive numbers become u32 without an `as` cast, everything else stays i32
 fn get_number(input: i32) -> Number {
-    let number = match input.is_positive() {
-        true => Number::U32(input as u32),
-        false => Number::I32(input),
-    };
-    number
+    let number = Number::I32(input);
+    match input.is_positive() {
+        true => number.convert_to(Kind::U32).unwrap_or(number),
+        false => number,
+    }
 }
 
 #[derive(Debug)]
@@ -262,19 +565,21 @@ impl Animal {
 
 impl Mood {
     fn check(&self) {
-        match self {
-            Mood::Happy => println!("happy"),
-            Mood::Angry => println!("angry"),
-            Mood::NotBad => println!("not bad"),
-            Mood::Sleepy => println!("sleepy"),
-        }
+        println!("{}", self.key());
+    }
+
+    // the name that mood scales use for this mood
+    fn key(&self) -> &'static str {
+        let (name, _) = mood_scale::builtin_mood(*self);
+        name
     }
 }
 
+#[derive(Debug)]
 struct Person {
     name: String,
     real_name: String,
-    height: u8,
+    height: Height,
     happiness: bool,
 }
 
@@ -282,7 +587,11 @@ fn check_if_happy(person: &Person) {
     println!("Is {} happy? {}", person.name, person.happiness);
 }
 
-fn check_if_happy_destructured(Person { name, happiness, .. }: &Person) {
+fn check_if_happy_destructured(
+    Person {
+        name, happiness, ..
+    }: &Person,
+) {
     println!("Is {name} happy? {happiness}");
 }
 
@@ -294,12 +603,7 @@ struct City {
 }
 
 impl City {
-    fn new(
-        name: &str,
-        name_before: &str,
-        population: u32,
-        date_founded: u32,
-    ) -> Self {
+    fn new(name: &str, name_before: &str, population: u32, date_founded: u32) -> Self {
         Self {
             name: String::from(name),
             name_before: String::from(name_before),
@@ -309,9 +613,7 @@ impl City {
     }
     fn print_names(&self) {
         let City {
-            name,
-            name_before,
-            ..
+            name, name_before, ..
         } = self;
         println!("the city {name} used to be called {name_before}");
     }
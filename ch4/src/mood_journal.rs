@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::date::{Date, DateTime};
+use crate::mood_scale::{MoodScale, ScaleError};
+
+// daily averages have to change by more than this per day to count as a trend
+const TREND_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Clone)]
+pub struct MoodEntry {
+    pub at: DateTime,
+    // the mood's name as the scale spells it
+    pub mood: String,
+    pub note: String,
+}
+
+// the same mood written down several times in a row
+#[derive(Debug, Clone, PartialEq)]
+pub struct Streak {
+    pub mood: String,
+    pub length: usize,
+    pub from: DateTime,
+    pub to: DateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trend {
+    Improving,
+    Steady,
+    Declining,
+    NotEnoughData,
+}
+
+// MoodJournal : timestamped moods, scored with a `MoodScale`
+// - entries are always kept in time order, even if they are recorded late
+// - only moods that the scale knows can be recorded, so every entry has a score
+#[derive(Debug)]
+pub struct MoodJournal {
+    entries: Vec<MoodEntry>,
+    scale: MoodScale,
+}
+
+impl Default for MoodJournal {
+    fn default() -> Self {
+        Self::with_scale(MoodScale::builtin())
+    }
+}
+
+impl MoodJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_scale(scale: MoodScale) -> Self {
+        Self {
+            entries: Vec::new(),
+            scale,
+        }
+    }
+
+    // `mood` is looked up in the scale without caring about case, like `MoodScale::get`
+    pub fn record(&mut self, at: DateTime, mood: &str, note: &str) -> Result<(), ScaleError> {
+        let Some(definition) = self.scale.get(mood) else {
+            return Err(ScaleError::UnknownMood(mood.to_string()));
+        };
+        let entry = MoodEntry {
+            at,
+            mood: definition.name.clone(),
+            note: note.to_string(),
+        };
+        let index = self.entries.partition_point(|entry| entry.at <= at);
+        self.entries.insert(index, entry);
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[MoodEntry] {
+        &self.entries
+    }
+
+    pub fn daily_averages(&self) -> BTreeMap<Date, f64> {
+        self.averages_by(|entry| entry.at.date())
+    }
+
+    // weeks start on Monday, and are keyed by that Monday
+    pub fn weekly_averages(&self) -> BTreeMap<Date, f64> {
+        self.averages_by(|entry| entry.at.date().start_of_week())
+    }
+
+    pub fn longest_streak(&self) -> Option<Streak> {
+        // max_by_key gives the last of equal streaks, so reverse to keep the first one
+        self.streaks()
+            .into_iter()
+            .rev()
+            .max_by_key(|streak| streak.length)
+    }
+
+    // the streak that the latest entry belongs to
+    pub fn current_streak(&self) -> Option<Streak> {
+        self.streaks().pop()
+    }
+
+    // every run of the same mood, including runs of one entry
+    pub fn streaks(&self) -> Vec<Streak> {
+        let mut streaks: Vec<Streak> = Vec::new();
+        for entry in &self.entries {
+            match streaks.last_mut() {
+                Some(streak) if streak.mood == entry.mood => {
+                    streak.length += 1;
+                    streak.to = entry.at;
+                }
+                _ => streaks.push(Streak {
+                    mood: entry.mood.clone(),
+                    length: 1,
+                    from: entry.at,
+                    to: entry.at,
+                }),
+            }
+        }
+        streaks
+    }
+
+    // fits a straight line through the daily averages (least squares)
+    // and looks at how steep it is
+    pub fn trend(&self) -> Trend {
+        let points = self
+            .daily_averages()
+            .into_iter()
+            .map(|(date, average)| (date.days_since_epoch() as f64, average))
+            .collect::<Vec<(f64, f64)>>();
+        if points.len() < 2 {
+            return Trend::NotEnoughData;
+        }
+
+        let count = points.len() as f64;
+        let mean_day = points.iter().map(|(day, _)| day).sum::<f64>() / count;
+        let mean_score = points.iter().map(|(_, score)| score).sum::<f64>() / count;
+        let covariance = points
+            .iter()
+            .map(|(day, score)| (day - mean_day) * (score - mean_score))
+            .sum::<f64>();
+        let variance = points
+            .iter()
+            .map(|(day, _)| (day - mean_day).powi(2))
+            .sum::<f64>();
+
+        match covariance / variance {
+            slope if slope > TREND_THRESHOLD => Trend::Improving,
+            slope if slope < -TREND_THRESHOLD => Trend::Declining,
+            _ => Trend::Steady,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time,mood,score,note\n");
+        for entry in &self.entries {
+            let score = match self.scale.score(&entry.mood) {
+                Some(score) => score.to_string(),
+                None => String::new(),
+            };
+            csv.push_str(&format!(
+                "{},{},{score},{}\n",
+                entry.at,
+                csv_field(&entry.mood),
+                csv_field(&entry.note),
+            ));
+        }
+        csv
+    }
+
+    pub fn export_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+
+    fn averages_by<K: Ord>(&self, key: impl Fn(&MoodEntry) -> K) -> BTreeMap<K, f64> {
+        let mut totals = BTreeMap::new();
+        for entry in &self.entries {
+            let Some(score) = self.scale.score(&entry.mood) else {
+                continue;
+            };
+            let (sum, count) = totals.entry(key(entry)).or_insert((0, 0));
+            *sum += score;
+            *count += 1;
+        }
+        totals
+            .into_iter()
+            .map(|(key, (sum, count))| (key, f64::from(sum) / f64::from(count)))
+            .collect()
+    }
+}
+
+// quotes a field if it has a comma, a quote or a line break in it
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime {
+        DateTime::new(Date::new(2024, 6, day).unwrap(), hour, 0).unwrap()
+    }
+
+    fn team_journal() -> MoodJournal {
+        let scale =
+            MoodScale::from_config_file(concat!(env!("CARGO_MANIFEST_DIR"), "/data/moods.ini"))
+                .unwrap();
+        MoodJournal::with_scale(scale)
+    }
+
+    #[test]
+    fn records_moods_that_only_the_config_has() {
+        let mut journal = team_journal();
+        journal.record(at(3, 9), "anxious", "").unwrap();
+        journal.record(at(3, 18), "Happy", "").unwrap();
+        journal.record(at(4, 9), "ANXIOUS", "").unwrap();
+        assert_eq!(journal.entries()[2].mood, "anxious");
+        let averages = journal.daily_averages().into_values().collect::<Vec<f64>>();
+        assert_eq!(averages, [3.0, 1.0]);
+        assert_eq!(
+            journal.longest_streak().map(|streak| streak.mood),
+            Some("anxious".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_moods_the_scale_does_not_know() {
+        let mut journal = MoodJournal::new();
+        assert!(
+            matches!(journal.record(at(3, 9), "anxious", ""), Err(ScaleError::UnknownMood(name)) if name == "anxious")
+        );
+        assert!(journal.entries().is_empty());
+        journal.record(at(3, 9), "not bad", "").unwrap();
+        assert_eq!(journal.entries().len(), 1);
+    }
+
+    #[test]
+    fn exports_csv_with_quoted_notes() {
+        let mut journal = MoodJournal::new();
+        journal.record(at(3, 8), "Sleepy", "early meeting").unwrap();
+        journal
+            .record(at(3, 20), "not bad", "train was late, \"again\"")
+            .unwrap();
+        let path =
+            std::env::temp_dir().join(format!("mood_journal_test_{}.csv", std::process::id()));
+        journal.export_csv(&path).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(written, journal.to_csv());
+        let lines = written.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "time,mood,score,note");
+        assert!(
+            lines[1].ends_with(",sleepy,6,early meeting"),
+            "{}",
+            lines[1]
+        );
+        assert!(
+            lines[2].ends_with(",not bad,7,\"train was late, \"\"again\"\"\""),
+            "{}",
+            lines[2]
+        );
+    }
+
+    #[test]
+    fn export_to_a_missing_directory_fails() {
+        let path = std::env::temp_dir()
+            .join("no_such_directory_for_moods")
+            .join("journal.csv");
+        assert!(MoodJournal::new().export_csv(path).is_err());
+    }
+
+    #[test]
+    fn entries_stay_in_time_order() {
+        let mut journal = team_journal();
+        journal.record(at(5, 9), "happy", "late").unwrap();
+        journal.record(at(3, 9), "angry", "early").unwrap();
+        journal.record(at(4, 9), "sleepy", "").unwrap();
+        let notes = journal
+            .entries()
+            .iter()
+            .map(|entry| entry.note.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(notes, ["early", "", "late"]);
+        assert_eq!(journal.trend(), Trend::Improving);
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Mood;
+
+// labels fall back to this language when a translation is missing
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+// every `Mood` with its name on a mood scale and its score out of 1 to 10
+// - `Mood` and `MoodScale::builtin` both read from here, so they can't disagree
+const BUILTIN_MOODS: [(Mood, &str, i32); 4] = [
+    (Mood::Happy, "happy", 10),
+    (Mood::Sleepy, "sleepy", 6),
+    (Mood::NotBad, "not bad", 7),
+    (Mood::Angry, "angry", 2),
+];
+
+// the name and score of `mood` on the built-in scale
+pub fn builtin_mood(mood: Mood) -> (&'static str, i32) {
+    BUILTIN_MOODS
+        .iter()
+        .find(|(builtin, _, _)| *builtin == mood)
+        .map(|&(_, name, score)| (name, score))
+        .expect("BUILTIN_MOODS has every Mood")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoodDefinition {
+    pub name: String,
+    pub score: i32,
+    // "#RRGGBB"
+    pub colour: Option<String>,
+    // language code -> label
+    pub labels: HashMap<String, String>,
+}
+
+// MoodScale : a set of moods with the score each one is worth
+// - every team can load its own from a config file (see data/moods.ini)
+// - scores have to be between `min` and `max`, and mood names have to be unique
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoodScale {
+    min: i32,
+    max: i32,
+    moods: Vec<MoodDefinition>,
+}
+
+impl MoodScale {
+    pub fn new(min: i32, max: i32, moods: Vec<MoodDefinition>) -> Result<Self, ScaleError> {
+        let scale = Self { min, max, moods };
+        scale.validate()?;
+        Ok(scale)
+    }
+
+    // the four moods of `Mood`, with the scores in BUILTIN_MOODS
+    pub fn builtin() -> Self {
+        let moods = BUILTIN_MOODS
+            .iter()
+            .map(|&(_, name, score)| MoodDefinition {
+                name: name.to_string(),
+                score,
+                colour: None,
+                labels: HashMap::from([(DEFAULT_LANGUAGE.to_string(), name.to_string())]),
+            })
+            .collect();
+        Self {
+            min: 1,
+            max: 10,
+            moods,
+        }
+    }
+
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, ScaleError> {
+        let text = fs::read_to_string(path)?;
+        Self::from_config_str(&text)
+    }
+
+    // the config looks like this:
+    //
+    // min = 0
+    // max = 10
+    //
+    // [happy]
+    // score = 10
+    // colour = #FFD400
+    // label.en = happy
+    // label.ko = 행복해요
+    //
+    // lines starting with # or ; are comments
+    pub fn from_config_str(text: &str) -> Result<Self, ScaleError> {
+        let mut min = None;
+        let mut max = None;
+        let mut moods: Vec<(usize, MoodDefinition, bool)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[') {
+                let Some(name) = section
+                    .strip_suffix(']')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                else {
+                    return Err(ScaleError::Syntax {
+                        line: line_number,
+                        message: format!("`{line}` is not a [mood name]"),
+                    });
+                };
+                let definition = MoodDefinition {
+                    name: name.to_string(),
+                    score: 0,
+                    colour: None,
+                    labels: HashMap::new(),
+                };
+                moods.push((line_number, definition, false));
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ScaleError::Syntax {
+                    line: line_number,
+                    message: format!("expected `key = value`, got `{line}`"),
+                });
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let number = || {
+                value.parse::<i32>().map_err(|_| ScaleError::Syntax {
+                    line: line_number,
+                    message: format!("`{value}` is not a whole number"),
+                })
+            };
+
+            match (moods.last_mut(), key) {
+                (None, "min") => min = Some(number()?),
+                (None, "max") => max = Some(number()?),
+                (Some((_, mood, has_score)), "score") => {
+                    mood.score = number()?;
+                    *has_score = true;
+                }
+                (Some((_, mood, _)), "colour" | "color") => mood.colour = Some(value.to_string()),
+                (Some((_, mood, _)), key) if key.starts_with("label.") => {
+                    let language = &key["label.".len()..];
+                    mood.labels.insert(language.to_string(), value.to_string());
+                }
+                _ => {
+                    return Err(ScaleError::Syntax {
+                        line: line_number,
+                        message: format!("unknown setting `{key}`"),
+                    })
+                }
+            }
+        }
+
+        let mut definitions = Vec::new();
+        for (line, mood, has_score) in moods {
+            if !has_score {
+                return Err(ScaleError::MissingScore {
+                    line,
+                    name: mood.name,
+                });
+            }
+            definitions.push(mood);
+        }
+        Self::new(
+            min.ok_or(ScaleError::MissingSetting("min"))?,
+            max.ok_or(ScaleError::MissingSetting("max"))?,
+            definitions,
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), ScaleError> {
+        if self.min > self.max {
+            return Err(ScaleError::MinAboveMax {
+                min: self.min,
+                max: self.max,
+            });
+        }
+        if self.moods.is_empty() {
+            return Err(ScaleError::NoMoods);
+        }
+        for (i, mood) in self.moods.iter().enumerate() {
+            let key = normalize(&mood.name);
+            if self.moods[..i]
+                .iter()
+                .any(|other| normalize(&other.name) == key)
+            {
+                return Err(ScaleError::DuplicateMood(mood.name.clone()));
+            }
+            if !(self.min..=self.max).contains(&mood.score) {
+                return Err(ScaleError::ScoreOutOfRange {
+                    name: mood.name.clone(),
+                    score: mood.score,
+                    min: self.min,
+                    max: self.max,
+                });
+            }
+            if let Some(colour) = mood.colour.as_ref().filter(|colour| !is_hex_colour(colour)) {
+                return Err(ScaleError::InvalidColour {
+                    name: mood.name.clone(),
+                    colour: colour.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn moods(&self) -> &[MoodDefinition] {
+        &self.moods
+    }
+
+    // mood names are matched without caring about case
+    pub fn get(&self, name: &str) -> Option<&MoodDefinition> {
+        let key = normalize(name);
+        self.moods.iter().find(|mood| normalize(&mood.name) == key)
+    }
+
+    pub fn score(&self, name: &str) -> Option<i32> {
+        self.get(name).map(|mood| mood.score)
+    }
+
+    // the label in `language`, then in DEFAULT_LANGUAGE, then the mood's own name
+    pub fn label(&self, name: &str, language: &str) -> Option<&str> {
+        let mood = self.get(name)?;
+        let label = mood
+            .labels
+            .get(language)
+            .or_else(|| mood.labels.get(DEFAULT_LANGUAGE))
+            .unwrap_or(&mood.name);
+        Some(label)
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn is_hex_colour(colour: &str) -> bool {
+    colour
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[derive(Debug)]
+pub enum ScaleError {
+    Io(io::Error),
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    MissingSetting(&'static str),
+    MissingScore {
+        line: usize,
+        name: String,
+    },
+    MinAboveMax {
+        min: i32,
+        max: i32,
+    },
+    NoMoods,
+    DuplicateMood(String),
+    ScoreOutOfRange {
+        name: String,
+        score: i32,
+        min: i32,
+        max: i32,
+    },
+    InvalidColour {
+        name: String,
+        colour: String,
+    },
+    // a mood that isn't on the scale, like one recorded in a journal
+    UnknownMood(String),
+}
+
+impl fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleError::Io(e) => write!(f, "could not read the mood scale: {e}"),
+            ScaleError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            ScaleError::MissingSetting(setting) => {
+                write!(f, "the mood scale needs a `{setting}` setting")
+            }
+            ScaleError::MissingScore { line, name } => {
+                write!(f, "line {line}: mood `{name}` has no score")
+            }
+            ScaleError::MinAboveMax { min, max } => {
+                write!(f, "min ({min}) is bigger than max ({max})")
+            }
+            ScaleError::NoMoods => write!(f, "the mood scale has no moods"),
+            ScaleError::DuplicateMood(name) => write!(f, "mood `{name}` is defined more than once"),
+            ScaleError::ScoreOutOfRange {
+                name,
+                score,
+                min,
+                max,
+            } => {
+                write!(
+                    f,
+                    "mood `{name}` has score {score}, but scores go from {min} to {max}"
+                )
+            }
+            ScaleError::InvalidColour { name, colour } => {
+                write!(
+                    f,
+                    "mood `{name}` has colour `{colour}`, expected something like #FFD400"
+                )
+            }
+            ScaleError::UnknownMood(name) => write!(f, "the mood scale has no mood `{name}`"),
+        }
+    }
+}
+
+impl Error for ScaleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScaleError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScaleError {
+    fn from(e: io::Error) -> Self {
+        ScaleError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale(moods: &str) -> Result<MoodScale, ScaleError> {
+        MoodScale::from_config_str(&format!("min = 0\nmax = 5\n{moods}"))
+    }
+
+    #[test]
+    fn builtin_agrees_with_mood() {
+        let scale = MoodScale::builtin();
+        for mood in [Mood::Happy, Mood::Sleepy, Mood::NotBad, Mood::Angry] {
+            assert_eq!(scale.score(mood.key()), Some(crate::match_mood(&mood)));
+            assert_eq!(scale.label(mood.key(), "en"), Some(mood.key()));
+        }
+        assert_eq!(scale.moods().len(), 4);
+        assert!(scale.validate().is_ok());
+    }
+
+    #[test]
+    fn reads_the_team_config() {
+        let scale =
+            MoodScale::from_config_file(concat!(env!("CARGO_MANIFEST_DIR"), "/data/moods.ini"))
+                .unwrap();
+        assert_eq!(scale.score("HAPPY"), Some(5));
+        assert_eq!(
+            scale.get(" not bad ").unwrap().colour.as_deref(),
+            Some("#8BC34A")
+        );
+        assert_eq!(scale.score("excited"), None);
+    }
+
+    #[test]
+    fn labels_fall_back_to_english_then_the_name() {
+        let scale = scale(
+            "[happy]\nscore = 5\nlabel.en = cheerful\nlabel.ko = 행복해요\n[tired]\nscore = 1",
+        )
+        .unwrap();
+        assert_eq!(scale.label("happy", "ko"), Some("행복해요"));
+        assert_eq!(scale.label("happy", "de"), Some("cheerful"));
+        assert_eq!(scale.label("tired", "ko"), Some("tired"));
+        assert_eq!(scale.label("bored", "en"), None);
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let error = scale("[happy]\nscore = 5\n[ Happy ]\nscore = 4").unwrap_err();
+        assert!(matches!(&error, ScaleError::DuplicateMood(name) if name == "Happy"));
+        assert_eq!(error.to_string(), "mood `Happy` is defined more than once");
+    }
+
+    #[test]
+    fn rejects_scores_out_of_range() {
+        let error = scale("[happy]\nscore = 6").unwrap_err();
+        assert!(matches!(
+            error,
+            ScaleError::ScoreOutOfRange {
+                score: 6,
+                min: 0,
+                max: 5,
+                ..
+            }
+        ));
+        assert!(matches!(
+            scale("[angry]\nscore = -1").unwrap_err(),
+            ScaleError::ScoreOutOfRange { score: -1, .. }
+        ));
+        assert!(scale("[angry]\nscore = 0\n[happy]\nscore = 5").is_ok());
+        assert!(matches!(
+            MoodScale::from_config_str("min = 5\nmax = 0\n[happy]\nscore = 5").unwrap_err(),
+            ScaleError::MinAboveMax { min: 5, max: 0 }
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_colours() {
+        for colour in ["FFD400", "#FFD40", "#FFD4000", "#GGD400", "yellow"] {
+            let error = scale(&format!("[happy]\nscore = 5\ncolour = {colour}")).unwrap_err();
+            assert!(
+                matches!(&error, ScaleError::InvalidColour { colour: c, .. } if c == colour),
+                "{colour}"
+            );
+        }
+        assert!(scale("[happy]\nscore = 5\ncolor = #ffd400").is_ok());
+    }
+
+    #[test]
+    fn config_errors_say_where() {
+        let error = scale("[happy]\nscore = lots").unwrap_err();
+        assert_eq!(error.to_string(), "line 4: `lots` is not a whole number");
+        let error = scale("[happy]\n[sleepy]\nscore = 2").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: mood `happy` has no score");
+        assert!(matches!(
+            scale("[]").unwrap_err(),
+            ScaleError::Syntax { line: 3, .. }
+        ));
+        assert!(matches!(
+            scale("[happy]\nsmell = nice").unwrap_err(),
+            ScaleError::Syntax { line: 4, .. }
+        ));
+        assert!(matches!(scale("").unwrap_err(), ScaleError::NoMoods));
+        assert!(matches!(
+            MoodScale::from_config_str("max = 5\n[happy]\nscore = 5").unwrap_err(),
+            ScaleError::MissingSetting("min")
+        ));
+        assert!(matches!(
+            MoodScale::from_config_file("no/such/file.ini").unwrap_err(),
+            ScaleError::Io(_)
+        ));
+    }
+}
@@ -0,0 +1,629 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Number : one value type for every integer width and both float widths
+// - arithmetic is checked: overflow and division by zero are errors, never wrapped or cast away
+// - two different kinds are first promoted to a kind that can hold both (see `Kind::promote`)
+// - `{}` prints the value, `{:#}` adds the kind like a Rust literal: `200u8`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    F32(f32),
+    F64(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+}
+
+impl Kind {
+    const ALL: [Kind; 12] = [
+        Kind::I8,
+        Kind::I16,
+        Kind::I32,
+        Kind::I64,
+        Kind::I128,
+        Kind::U8,
+        Kind::U16,
+        Kind::U32,
+        Kind::U64,
+        Kind::U128,
+        Kind::F32,
+        Kind::F64,
+    ];
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            Kind::I8 | Kind::U8 => 8,
+            Kind::I16 | Kind::U16 => 16,
+            Kind::I32 | Kind::U32 | Kind::F32 => 32,
+            Kind::I64 | Kind::U64 | Kind::F64 => 64,
+            Kind::I128 | Kind::U128 => 128,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Kind::F32 | Kind::F64)
+    }
+
+    pub fn is_signed(&self) -> bool {
+        !matches!(
+            self,
+            Kind::U8 | Kind::U16 | Kind::U32 | Kind::U64 | Kind::U128
+        )
+    }
+
+    // the kind that two numbers are turned into before doing math on them:
+    // - the same kind stays the same
+    // - any float makes it a float (f32 only if both are f32)
+    // - same signedness takes the wider one
+    // - signed and unsigned take a signed kind wide enough for the unsigned one (at most i128)
+    pub fn promote(a: Kind, b: Kind) -> Kind {
+        if a == b {
+            return a;
+        }
+        if a.is_float() || b.is_float() {
+            return Kind::F64;
+        }
+        if a.is_signed() == b.is_signed() {
+            return if a.bits() >= b.bits() { a } else { b };
+        }
+
+        let (signed, unsigned) = if a.is_signed() { (a, b) } else { (b, a) };
+        if signed.bits() > unsigned.bits() {
+            signed
+        } else {
+            match unsigned.bits() {
+                8 => Kind::I16,
+                16 => Kind::I32,
+                32 => Kind::I64,
+                _ => Kind::I128,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("{self:?}").to_lowercase();
+        f.write_str(&name)
+    }
+}
+
+// every Number fits in one of these without losing anything
+#[derive(Debug, Clone, Copy)]
+enum Wide {
+    Signed(i128),
+    Unsigned(u128),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Number {
+    pub fn kind(&self) -> Kind {
+        match self {
+            Number::I8(_) => Kind::I8,
+            Number::I16(_) => Kind::I16,
+            Number::I32(_) => Kind::I32,
+            Number::I64(_) => Kind::I64,
+            Number::I128(_) => Kind::I128,
+            Number::U8(_) => Kind::U8,
+            Number::U16(_) => Kind::U16,
+            Number::U32(_) => Kind::U32,
+            Number::U64(_) => Kind::U64,
+            Number::U128(_) => Kind::U128,
+            Number::F32(_) => Kind::F32,
+            Number::F64(_) => Kind::F64,
+        }
+    }
+
+    // the same value as another kind, or an error if it doesn't fit exactly
+    pub fn convert_to(self, kind: Kind) -> Result<Number, NumberError> {
+        Number::from_wide(self.wide(), kind)
+    }
+
+    pub fn checked_add(self, other: Number) -> Result<Number, NumberError> {
+        self.apply(Operation::Add, other)
+    }
+
+    pub fn checked_sub(self, other: Number) -> Result<Number, NumberError> {
+        self.apply(Operation::Sub, other)
+    }
+
+    pub fn checked_mul(self, other: Number) -> Result<Number, NumberError> {
+        self.apply(Operation::Mul, other)
+    }
+
+    // integer division rounds towards zero, like `/` does
+    pub fn checked_div(self, other: Number) -> Result<Number, NumberError> {
+        self.apply(Operation::Div, other)
+    }
+
+    fn apply(self, operation: Operation, other: Number) -> Result<Number, NumberError> {
+        let kind = Kind::promote(self.kind(), other.kind());
+        let a = self.convert_to(kind)?.wide();
+        let b = other.convert_to(kind)?.wide();
+        let overflow = NumberError::Overflow { kind };
+
+        let result = match (a, b) {
+            (Wide::Signed(a), Wide::Signed(b)) => {
+                let result = match operation {
+                    Operation::Add => a.checked_add(b),
+                    Operation::Sub => a.checked_sub(b),
+                    Operation::Mul => a.checked_mul(b),
+                    Operation::Div if b == 0 => return Err(NumberError::DivisionByZero),
+                    Operation::Div => a.checked_div(b),
+                };
+                Wide::Signed(result.ok_or(overflow)?)
+            }
+            (Wide::Unsigned(a), Wide::Unsigned(b)) => {
+                let result = match operation {
+                    Operation::Add => a.checked_add(b),
+                    Operation::Sub => a.checked_sub(b),
+                    Operation::Mul => a.checked_mul(b),
+                    Operation::Div if b == 0 => return Err(NumberError::DivisionByZero),
+                    Operation::Div => a.checked_div(b),
+                };
+                Wide::Unsigned(result.ok_or(overflow)?)
+            }
+            (Wide::Float(a), Wide::Float(b)) => {
+                let result = match operation {
+                    Operation::Add => a + b,
+                    Operation::Sub => a - b,
+                    Operation::Mul => a * b,
+                    Operation::Div if b == 0.0 => return Err(NumberError::DivisionByZero),
+                    Operation::Div => a / b,
+                };
+                if result.is_infinite() && a.is_finite() && b.is_finite() {
+                    return Err(overflow);
+                }
+                if kind == Kind::F32 {
+                    // f32 math rounds its result, the same as `a + b` on two f32s would
+                    let small = result as f32;
+                    if small.is_infinite() && result.is_finite() {
+                        return Err(overflow);
+                    }
+                    Wide::Float(small.into())
+                } else {
+                    Wide::Float(result)
+                }
+            }
+            _ => unreachable!("both sides were converted to {kind}"),
+        };
+        Number::from_wide(result, kind)
+    }
+
+    fn wide(self) -> Wide {
+        match self {
+            Number::I8(n) => Wide::Signed(n.into()),
+            Number::I16(n) => Wide::Signed(n.into()),
+            Number::I32(n) => Wide::Signed(n.into()),
+            Number::I64(n) => Wide::Signed(n.into()),
+            Number::I128(n) => Wide::Signed(n),
+            Number::U8(n) => Wide::Unsigned(n.into()),
+            Number::U16(n) => Wide::Unsigned(n.into()),
+            Number::U32(n) => Wide::Unsigned(n.into()),
+            Number::U64(n) => Wide::Unsigned(n.into()),
+            Number::U128(n) => Wide::Unsigned(n),
+            Number::F32(n) => Wide::Float(n.into()),
+            Number::F64(n) => Wide::Float(n),
+        }
+    }
+
+    fn from_wide(wide: Wide, kind: Kind) -> Result<Number, NumberError> {
+        let inexact = || NumberError::Inexact { kind };
+        let overflow = || NumberError::Overflow { kind };
+
+        if kind.is_float() {
+            let value = match wide {
+                Wide::Float(value) => value,
+                // an integer only goes into a float if it has no more significant bits than the float keeps
+                Wide::Signed(n) if significant_bits(n.unsigned_abs()) <= f64::MANTISSA_DIGITS => {
+                    n as f64
+                }
+                Wide::Unsigned(n) if significant_bits(n) <= f64::MANTISSA_DIGITS => n as f64,
+                _ => return Err(inexact()),
+            };
+            return match kind {
+                Kind::F32 => {
+                    let small = value as f32;
+                    if small.is_infinite() && value.is_finite() {
+                        Err(overflow())
+                    } else if !value.is_nan() && f64::from(small) != value {
+                        Err(inexact())
+                    } else {
+                        Ok(Number::F32(small))
+                    }
+                }
+                _ => Ok(Number::F64(value)),
+            };
+        }
+
+        // a float only goes into an integer if it is a whole number
+        let wide = match wide {
+            Wide::Float(value) if !value.is_finite() || value.fract() != 0.0 => {
+                return Err(inexact())
+            }
+            Wide::Float(value) if value < 0.0 => {
+                if value < i128::MIN as f64 {
+                    return Err(overflow());
+                }
+                Wide::Signed(value as i128)
+            }
+            Wide::Float(value) => {
+                if value >= u128::MAX as f64 {
+                    return Err(overflow());
+                }
+                Wide::Unsigned(value as u128)
+            }
+            wide => wide,
+        };
+
+        if kind == Kind::U128 {
+            return match wide {
+                Wide::Unsigned(n) => Ok(Number::U128(n)),
+                Wide::Signed(n) => u128::try_from(n).map(Number::U128).map_err(|_| overflow()),
+                Wide::Float(_) => unreachable!("floats were turned into integers above"),
+            };
+        }
+        let n = match wide {
+            Wide::Signed(n) => n,
+            Wide::Unsigned(n) => i128::try_from(n).map_err(|_| overflow())?,
+            Wide::Float(_) => unreachable!("floats were turned into integers above"),
+        };
+        let number = match kind {
+            Kind::I8 => i8::try_from(n).map(Number::I8),
+            Kind::I16 => i16::try_from(n).map(Number::I16),
+            Kind::I32 => i32::try_from(n).map(Number::I32),
+            Kind::I64 => i64::try_from(n).map(Number::I64),
+            Kind::I128 => Ok(Number::I128(n)),
+            Kind::U8 => u8::try_from(n).map(Number::U8),
+            Kind::U16 => u16::try_from(n).map(Number::U16),
+            Kind::U32 => u32::try_from(n).map(Number::U32),
+            Kind::U64 => u64::try_from(n).map(Number::U64),
+            Kind::U128 | Kind::F32 | Kind::F64 => unreachable!("handled above"),
+        };
+        number.map_err(|_| overflow())
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::I8(n) => write!(f, "{n}")?,
+            Number::I16(n) => write!(f, "{n}")?,
+            Number::I32(n) => write!(f, "{n}")?,
+            Number::I64(n) => write!(f, "{n}")?,
+            Number::I128(n) => write!(f, "{n}")?,
+            Number::U8(n) => write!(f, "{n}")?,
+            Number::U16(n) => write!(f, "{n}")?,
+            Number::U32(n) => write!(f, "{n}")?,
+            Number::U64(n) => write!(f, "{n}")?,
+            Number::U128(n) => write!(f, "{n}")?,
+            Number::F32(n) => write!(f, "{n}")?,
+            Number::F64(n) => write!(f, "{n}")?,
+        }
+        if f.alternate() {
+            write!(f, "{}", self.kind())?;
+        }
+        Ok(())
+    }
+}
+
+// parses Rust-style literals:
+// - "200u8", "-5i64", "2.5f32" give that kind
+// - without a suffix, "1.5", "1e3", "inf" and "NaN" are f64,
+//   and whole numbers are the first of i32, i64, i128, u128 that they fit in
+// - `_` can be used to group digits, like "1_000_000"
+impl FromStr for Number {
+    type Err = NumberError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || NumberError::Invalid(input.to_string());
+        let text = input.trim().replace('_', "");
+
+        let suffix = Kind::ALL
+            .into_iter()
+            .find(|kind| text.ends_with(&kind.to_string()));
+        let digits = match suffix {
+            Some(kind) => &text[..text.len() - kind.to_string().len()],
+            None => text.as_str(),
+        };
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+
+        if let Some(kind) = suffix {
+            if kind == Kind::F32 {
+                // parsed as f32 straight away, so "0.1f32" is the nearest f32 and not an inexact f64
+                let value = digits.parse::<f32>().map_err(|_| invalid())?;
+                if value.is_infinite() && digits.parse::<f64>().is_ok_and(f64::is_finite) {
+                    return Err(NumberError::Overflow { kind });
+                }
+                return Ok(Number::F32(value));
+            }
+            let wide = if kind.is_float() {
+                Wide::Float(digits.parse::<f64>().map_err(|_| invalid())?)
+            } else if let Ok(n) = digits.parse::<i128>() {
+                Wide::Signed(n)
+            } else if let Ok(n) = digits.parse::<u128>() {
+                Wide::Unsigned(n)
+            } else if is_integer_literal(digits) {
+                return Err(NumberError::Overflow { kind });
+            } else {
+                return Err(invalid());
+            };
+            return Number::from_wide(wide, kind);
+        }
+
+        if let Ok(n) = digits.parse::<i128>() {
+            let number = Number::I128(n);
+            return Ok(number
+                .convert_to(Kind::I32)
+                .or_else(|_| number.convert_to(Kind::I64))
+                .unwrap_or(number));
+        }
+        if let Ok(n) = digits.parse::<u128>() {
+            return Ok(Number::U128(n));
+        }
+        if is_integer_literal(digits) {
+            return Err(NumberError::Overflow { kind: Kind::U128 });
+        }
+        digits
+            .parse::<f64>()
+            .map(Number::F64)
+            .map_err(|_| invalid())
+    }
+}
+
+// digits with at most one sign in front, so "--5" and "-" aren't taken for numbers too big to fit
+fn is_integer_literal(digits: &str) -> bool {
+    let unsigned = digits.strip_prefix(['-', '+']).unwrap_or(digits);
+    !unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit())
+}
+
+// the bits from the highest one to the lowest one, which is what a float has to keep
+fn significant_bits(n: u128) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        u128::BITS - n.leading_zeros() - n.trailing_zeros()
+    }
+}
+
+impl From<i8> for Number {
+    fn from(n: i8) -> Self {
+        Number::I8(n)
+    }
+}
+
+impl From<i16> for Number {
+    fn from(n: i16) -> Self {
+        Number::I16(n)
+    }
+}
+
+impl From<i32> for Number {
+    fn from(n: i32) -> Self {
+        Number::I32(n)
+    }
+}
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
+        Number::I64(n)
+    }
+}
+
+impl From<i128> for Number {
+    fn from(n: i128) -> Self {
+        Number::I128(n)
+    }
+}
+
+impl From<u8> for Number {
+    fn from(n: u8) -> Self {
+        Number::U8(n)
+    }
+}
+
+impl From<u16> for Number {
+    fn from(n: u16) -> Self {
+        Number::U16(n)
+    }
+}
+
+impl From<u32> for Number {
+    fn from(n: u32) -> Self {
+        Number::U32(n)
+    }
+}
+
+impl From<u64> for Number {
+    fn from(n: u64) -> Self {
+        Number::U64(n)
+    }
+}
+
+impl From<u128> for Number {
+    fn from(n: u128) -> Self {
+        Number::U128(n)
+    }
+}
+
+impl From<f32> for Number {
+    fn from(n: f32) -> Self {
+        Number::F32(n)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(n: f64) -> Self {
+        Number::F64(n)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberError {
+    // the value is too big or too small for the kind
+    Overflow { kind: Kind },
+    // the kind can't hold the value exactly, like 0.5 as an integer
+    Inexact { kind: Kind },
+    DivisionByZero,
+    Invalid(String),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::Overflow { kind } => write!(f, "the number doesn't fit in {kind}"),
+            NumberError::Inexact { kind } => {
+                write!(f, "the number can't be stored exactly as {kind}")
+            }
+            NumberError::DivisionByZero => write!(f, "division by zero"),
+            NumberError::Invalid(input) => write!(f, "{input:?} is not a number"),
+        }
+    }
+}
+
+impl Error for NumberError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_integers_are_not_exact_floats() {
+        let inexact = NumberError::Inexact { kind: Kind::F64 };
+        assert_eq!(
+            Number::I128(i128::MAX).convert_to(Kind::F64),
+            Err(inexact.clone())
+        );
+        assert_eq!(
+            Number::U128(u128::MAX).convert_to(Kind::F64),
+            Err(inexact.clone())
+        );
+        assert_eq!(
+            Number::I64((1 << 53) + 1).convert_to(Kind::F64),
+            Err(inexact)
+        );
+        assert_eq!(
+            Number::I128(i128::MIN).convert_to(Kind::F64),
+            Ok(Number::F64(-(2f64.powi(127))))
+        );
+        assert_eq!(
+            Number::U128(1 << 127).convert_to(Kind::F64),
+            Ok(Number::F64(2f64.powi(127)))
+        );
+        assert_eq!(
+            Number::I64(1 << 53).convert_to(Kind::F64),
+            Ok(Number::F64(2f64.powi(53)))
+        );
+        assert_eq!(
+            Number::I32((1 << 24) + 1).convert_to(Kind::F32),
+            Err(NumberError::Inexact { kind: Kind::F32 })
+        );
+        assert_eq!(
+            Number::I32(-(1 << 24)).convert_to(Kind::F32),
+            Ok(Number::F32(-16_777_216.0))
+        );
+    }
+
+    #[test]
+    fn f64_to_f32_must_be_exact() {
+        assert_eq!(
+            Number::F64(0.1).convert_to(Kind::F32),
+            Err(NumberError::Inexact { kind: Kind::F32 })
+        );
+        assert_eq!(Number::F64(0.5).convert_to(Kind::F32), Ok(Number::F32(0.5)));
+        assert_eq!(
+            Number::F64(1e300).convert_to(Kind::F32),
+            Err(NumberError::Overflow { kind: Kind::F32 })
+        );
+        assert_eq!(
+            Number::F64(f64::INFINITY).convert_to(Kind::F32),
+            Ok(Number::F32(f32::INFINITY))
+        );
+        assert!(
+            matches!(Number::F64(f64::NAN).convert_to(Kind::F32), Ok(Number::F32(n)) if n.is_nan())
+        );
+        assert_eq!(
+            Number::F32(0.1).convert_to(Kind::F64),
+            Ok(Number::F64(f64::from(0.1f32)))
+        );
+    }
+
+    #[test]
+    fn f32_math_and_literals_round_to_f32() {
+        assert_eq!("0.1f32".parse::<Number>(), Ok(Number::F32(0.1)));
+        assert_eq!(
+            "1e39f32".parse::<Number>(),
+            Err(NumberError::Overflow { kind: Kind::F32 })
+        );
+        assert_eq!(
+            Number::F32(0.1).checked_add(Number::F32(0.2)),
+            Ok(Number::F32(0.1 + 0.2))
+        );
+        assert_eq!(
+            Number::F32(f32::MAX).checked_mul(Number::F32(2.0)),
+            Err(NumberError::Overflow { kind: Kind::F32 })
+        );
+    }
+
+    #[test]
+    fn only_digits_overflow() {
+        for input in ["-", "+", "--5", "+-5", "-u8", "+i64"] {
+            assert_eq!(
+                input.parse::<Number>(),
+                Err(NumberError::Invalid(input.to_string())),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            "300u8".parse::<Number>(),
+            Err(NumberError::Overflow { kind: Kind::U8 })
+        );
+        assert_eq!(
+            "-1u8".parse::<Number>(),
+            Err(NumberError::Overflow { kind: Kind::U8 })
+        );
+        let too_big = format!("{}0", u128::MAX);
+        assert_eq!(
+            too_big.parse::<Number>(),
+            Err(NumberError::Overflow { kind: Kind::U128 })
+        );
+        assert_eq!(
+            format!("+{too_big}i128").parse::<Number>(),
+            Err(NumberError::Overflow { kind: Kind::I128 })
+        );
+    }
+}
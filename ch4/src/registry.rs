@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::Person;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PersonId(usize);
+
+#[derive(Debug)]
+struct Entry {
+    person: Person,
+    aliases: Vec<String>,
+}
+
+// Registry : finds people by their name, their real name or any alias
+// - names are matched without caring about case or extra spaces
+// - a name can only point to one person
+#[derive(Debug, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+    names: HashMap<String, PersonId>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // both names have to be free, and neither can be empty
+    pub fn register(&mut self, person: Person) -> Result<PersonId, RegistryError> {
+        for name in [&person.name, &person.real_name] {
+            if name.trim().is_empty() {
+                return Err(RegistryError::EmptyName);
+            }
+            self.check_free(name, None)?;
+        }
+
+        let id = PersonId(self.entries.len());
+        self.names.insert(normalize(&person.name), id);
+        self.names.insert(normalize(&person.real_name), id);
+        self.entries.push(Entry {
+            person,
+            aliases: Vec::new(),
+        });
+        Ok(id)
+    }
+
+    // adding a name the person already has does nothing
+    pub fn add_alias(&mut self, id: PersonId, alias: &str) -> Result<(), RegistryError> {
+        if id.0 >= self.entries.len() {
+            return Err(RegistryError::UnknownPerson(id));
+        }
+        let alias = alias.trim();
+        if alias.is_empty() {
+            return Err(RegistryError::EmptyName);
+        }
+        if self.check_free(alias, Some(id))? {
+            self.names.insert(normalize(alias), id);
+            self.entries[id.0].aliases.push(alias.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: PersonId) -> Option<&Person> {
+        self.entries.get(id.0).map(|entry| &entry.person)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Person> {
+        let id = self.names.get(&normalize(name))?;
+        self.get(*id)
+    }
+
+    // "who is X really?"
+    pub fn who_is(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|person| person.real_name.as_str())
+    }
+
+    // the stage name, then the real name, then the aliases in the order they were added
+    pub fn names_of(&self, id: PersonId) -> Vec<&str> {
+        let Some(entry) = self.entries.get(id.0) else {
+            return Vec::new();
+        };
+        let mut names = vec![entry.person.name.as_str()];
+        if normalize(&entry.person.real_name) != normalize(&entry.person.name) {
+            names.push(&entry.person.real_name);
+        }
+        names.extend(entry.aliases.iter().map(|alias| alias.as_str()));
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    // Ok(true) if nobody has the name, Ok(false) if `owner` already has it
+    fn check_free(&self, name: &str, owner: Option<PersonId>) -> Result<bool, RegistryError> {
+        match self.names.get(&normalize(name)) {
+            None => Ok(true),
+            Some(id) if Some(*id) == owner => Ok(false),
+            Some(id) => Err(RegistryError::NameTaken {
+                name: name.to_string(),
+                taken_by: self.entries[id.0].person.real_name.clone(),
+            }),
+        }
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RegistryError {
+    NameTaken { name: String, taken_by: String },
+    UnknownPerson(PersonId),
+    EmptyName,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::NameTaken { name, taken_by } => {
+                write!(f, "the name {name} already belongs to {taken_by}")
+            }
+            RegistryError::UnknownPerson(id) => write!(f, "there is no person with id {}", id.0),
+            RegistryError::EmptyName => write!(f, "a name can't be empty"),
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::height::Height;
+
+    fn person(name: &str, real_name: &str) -> Person {
+        Person {
+            name: name.to_string(),
+            real_name: real_name.to_string(),
+            height: Height::from_cm(170.0).unwrap(),
+            happiness: true,
+        }
+    }
+
+    #[test]
+    fn register_rejects_empty_names() {
+        let mut registry = Registry::new();
+        for (name, real_name) in [
+            ("", "Clarence"),
+            ("Papa Doc", ""),
+            ("  ", "Clarence"),
+            ("Papa Doc", "\t\n"),
+        ] {
+            assert_eq!(
+                registry.register(person(name, real_name)),
+                Err(RegistryError::EmptyName)
+            );
+        }
+        assert_eq!(registry.len(), 0);
+        assert!(registry.find("").is_none());
+    }
+
+    #[test]
+    fn finds_by_every_name() {
+        let mut registry = Registry::new();
+        let id = registry.register(person("Papa Doc", "Clarence")).unwrap();
+        registry.add_alias(id, "  The   Doctor ").unwrap();
+        for name in ["papa doc", "PAPA  DOC", "clarence", "the doctor"] {
+            assert_eq!(registry.who_is(name), Some("Clarence"), "{name}");
+        }
+        assert_eq!(
+            registry.names_of(id),
+            ["Papa Doc", "Clarence", "The   Doctor"]
+        );
+        assert_eq!(registry.who_is("Emmett"), None);
+    }
+
+    #[test]
+    fn the_same_name_twice_is_listed_once() {
+        let mut registry = Registry::new();
+        let id = registry.register(person("Cher", "cher")).unwrap();
+        registry.add_alias(id, "CHER").unwrap();
+        assert_eq!(registry.names_of(id), ["Cher"]);
+    }
+
+    #[test]
+    fn names_belong_to_one_person() {
+        let mut registry = Registry::new();
+        let doc = registry.register(person("Papa Doc", "Clarence")).unwrap();
+        registry.add_alias(doc, "Doc").unwrap();
+
+        let taken = RegistryError::NameTaken {
+            name: "doc".to_string(),
+            taken_by: "Clarence".to_string(),
+        };
+        assert_eq!(registry.register(person("doc", "Emmett")), Err(taken));
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.who_is("Emmett"), None);
+
+        let emmett = registry.register(person("Doc Brown", "Emmett")).unwrap();
+        assert!(matches!(
+            registry.add_alias(emmett, "DOC"),
+            Err(RegistryError::NameTaken { .. })
+        ));
+        assert_eq!(
+            registry
+                .add_alias(emmett, "Clarence")
+                .unwrap_err()
+                .to_string(),
+            "the name Clarence already belongs to Clarence"
+        );
+    }
+
+    #[test]
+    fn add_alias_checks_the_person_and_the_name() {
+        let mut registry = Registry::new();
+        let id = registry.register(person("Papa Doc", "Clarence")).unwrap();
+        assert_eq!(registry.add_alias(id, "   "), Err(RegistryError::EmptyName));
+        assert_eq!(
+            registry.add_alias(PersonId(7), "Doc"),
+            Err(RegistryError::UnknownPerson(PersonId(7)))
+        );
+        assert_eq!(
+            registry.get(PersonId(7)).map(|person| person.name.as_str()),
+            None
+        );
+        assert!(registry.names_of(PersonId(7)).is_empty());
+        assert_eq!(
+            RegistryError::EmptyName.to_string(),
+            "a name can't be empty"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::date::{Date, DateTime};
+
+// the sun's centre is this far below the horizon (degrees) at sunrise and sunset,
+// because the air bends the light and the sun isn't just a point
+pub const SUNRISE_ELEVATION: f64 = -0.833;
+pub const CIVIL_TWILIGHT_ELEVATION: f64 = -6.0;
+pub const NAUTICAL_TWILIGHT_ELEVATION: f64 = -12.0;
+pub const ASTRONOMICAL_TWILIGHT_ELEVATION: f64 = -18.0;
+
+// somewhere on earth, in degrees: north and east are positive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observer {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+// sunrise and sunset in hours after midnight UTC of the requested date,
+// they can be below 0 or above 24 when the local day doesn't line up with UTC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Daylight {
+    RiseAndSet { sunrise: f64, sunset: f64 },
+    MidnightSun,
+    PolarNight,
+}
+
+// how high the sun is above the horizon in degrees (negative when it is below)
+pub fn solar_elevation(observer: &Observer, at: &DateTime) -> f64 {
+    let minutes = f64::from(at.minutes_since_midnight());
+    let sun = SunPosition::at(julian_day(&at.date(), minutes / 60.0));
+
+    // the sun moves 1 degree of longitude every 4 minutes
+    let true_solar_time = minutes + sun.equation_of_time + 4.0 * observer.longitude;
+    let hour_angle = (true_solar_time / 4.0 - 180.0).to_radians();
+
+    let latitude = observer.latitude.to_radians();
+    let declination = sun.declination.to_radians();
+    let cos_zenith =
+        latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos();
+    90.0 - cos_zenith.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+pub fn daylight(observer: &Observer, date: &Date) -> Daylight {
+    sun_crossing(observer, date, SUNRISE_ELEVATION)
+}
+
+// when the sun passes `elevation` in the morning and the evening,
+// with SUNRISE_ELEVATION this is sunrise and sunset, with the twilight ones it's dawn and dusk
+pub fn sun_crossing(observer: &Observer, date: &Date, elevation: f64) -> Daylight {
+    // first guess at noon, then once more at the solar noon we found
+    let mut noon = 12.0 - observer.longitude / 15.0;
+    let mut sun = SunPosition::at(julian_day(date, noon));
+    noon = (720.0 - 4.0 * observer.longitude - sun.equation_of_time) / 60.0;
+    sun = SunPosition::at(julian_day(date, noon));
+
+    // at a pole the sun goes round at the same height all day, and cos(latitude) is 0 below
+    if observer.latitude.abs() >= 90.0 {
+        let height = sun.declination * observer.latitude.signum();
+        return if height > elevation {
+            Daylight::MidnightSun
+        } else {
+            Daylight::PolarNight
+        };
+    }
+
+    let latitude = observer.latitude.to_radians();
+    let declination = sun.declination.to_radians();
+    let cos_hour_angle = (elevation.to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+
+    match cos_hour_angle {
+        cos if cos > 1.0 => Daylight::PolarNight,
+        cos if cos < -1.0 => Daylight::MidnightSun,
+        cos => {
+            let half_day = cos.acos().to_degrees() / 15.0;
+            Daylight::RiseAndSet {
+                sunrise: noon - half_day,
+                sunset: noon + half_day,
+            }
+        }
+    }
+}
+
+// 6.5 -> "06:30"
+pub fn format_hours(hours: f64) -> String {
+    let minutes = (hours * 60.0).round().rem_euclid(24.0 * 60.0) as u32;
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+// days since noon on January 1st, 4713 BC, which astronomers count from
+fn julian_day(date: &Date, hours_utc: f64) -> f64 {
+    date.days_since_epoch() as f64 + 2_440_587.5 + hours_utc / 24.0
+}
+
+// the NOAA solar calculator's formulas, good to about a minute for years 1800..2100
+struct SunPosition {
+    // degrees
+    declination: f64,
+    // minutes that a sundial is ahead of a clock
+    equation_of_time: f64,
+}
+
+impl SunPosition {
+    fn at(julian_day: f64) -> Self {
+        let t = (julian_day - 2_451_545.0) / 36_525.0;
+
+        let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+        let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+        let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+
+        let m = mean_anomaly.to_radians();
+        let equation_of_centre = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+            + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+            + (3.0 * m).sin() * 0.000289;
+
+        let omega = (125.04 - 1934.136 * t).to_radians();
+        let apparent_longitude =
+            (mean_longitude + equation_of_centre - 0.00569 - 0.00478 * omega.sin()).to_radians();
+
+        let mean_obliquity =
+            23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+        let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+
+        let declination = (obliquity.sin() * apparent_longitude.sin())
+            .asin()
+            .to_degrees();
+
+        let y = (obliquity / 2.0).tan().powi(2);
+        let l = mean_longitude.to_radians();
+        let equation_of_time = 4.0
+            * (y * (2.0 * l).sin() - 2.0 * eccentricity * m.sin()
+                + 4.0 * eccentricity * y * m.sin() * (2.0 * l).cos()
+                - 0.5 * y * y * (4.0 * l).sin()
+                - 1.25 * eccentricity * eccentricity * (2.0 * m).sin())
+            .to_degrees();
+
+        Self {
+            declination,
+            equation_of_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TALLINN: Observer = Observer {
+        latitude: 59.437,
+        longitude: 24.7536,
+    };
+
+    fn date(month: u32, day: u32) -> Date {
+        Date::new(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn tallinn_at_midsummer() {
+        let Daylight::RiseAndSet { sunrise, sunset } = daylight(&TALLINN, &date(6, 21)) else {
+            panic!("the sun rises and sets in Tallinn");
+        };
+        assert_eq!(format_hours(sunrise), "01:03");
+        assert_eq!(format_hours(sunset), "19:43");
+        // the sun never sinks as far as nautical twilight, so it is "up" all night for that elevation
+        assert_eq!(
+            sun_crossing(&TALLINN, &date(6, 21), NAUTICAL_TWILIGHT_ELEVATION),
+            Daylight::MidnightSun
+        );
+    }
+
+    #[test]
+    fn polar_night_and_midnight_sun() {
+        let tromso = Observer {
+            latitude: 69.65,
+            longitude: 18.96,
+        };
+        assert_eq!(daylight(&tromso, &date(12, 21)), Daylight::PolarNight);
+        assert_eq!(daylight(&tromso, &date(6, 21)), Daylight::MidnightSun);
+        let mcmurdo = Observer {
+            latitude: -77.85,
+            longitude: 166.67,
+        };
+        assert_eq!(daylight(&mcmurdo, &date(12, 21)), Daylight::MidnightSun);
+        assert_eq!(daylight(&mcmurdo, &date(6, 21)), Daylight::PolarNight);
+    }
+
+    #[test]
+    fn poles() {
+        for longitude in [0.0, 180.0, -45.0] {
+            let north = Observer {
+                latitude: 90.0,
+                longitude,
+            };
+            let south = Observer {
+                latitude: -90.0,
+                longitude,
+            };
+            assert_eq!(daylight(&north, &date(6, 21)), Daylight::MidnightSun);
+            assert_eq!(daylight(&north, &date(12, 21)), Daylight::PolarNight);
+            assert_eq!(daylight(&south, &date(6, 21)), Daylight::PolarNight);
+            assert_eq!(daylight(&south, &date(12, 21)), Daylight::MidnightSun);
+        }
+        // a few days after the equinox the sun is just above the north pole's horizon
+        let north = Observer {
+            latitude: 90.0,
+            longitude: 0.0,
+        };
+        assert_eq!(daylight(&north, &date(3, 25)), Daylight::MidnightSun);
+        assert_eq!(
+            sun_crossing(&north, &date(3, 25), CIVIL_TWILIGHT_ELEVATION),
+            Daylight::MidnightSun
+        );
+        assert_eq!(
+            sun_crossing(&north, &date(3, 15), SUNRISE_ELEVATION),
+            Daylight::PolarNight
+        );
+    }
+
+    #[test]
+    fn elevation_at_noon_and_midnight() {
+        let noon = DateTime::new(date(6, 21), 10, 20).unwrap();
+        let midnight = DateTime::new(date(6, 21), 22, 20).unwrap();
+        assert!((solar_elevation(&TALLINN, &noon) - 54.0).abs() < 0.5);
+        assert!(solar_elevation(&TALLINN, &midnight) > NAUTICAL_TWILIGHT_ELEVATION);
+        assert!(solar_elevation(&TALLINN, &midnight) < CIVIL_TWILIGHT_ELEVATION);
+    }
+}
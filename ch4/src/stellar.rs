@@ -0,0 +1,324 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Star;
+
+// below this many suns of mass, a star never gets hot enough to burn hydrogen
+pub const HYDROGEN_BURNING_LIMIT: f64 = 0.08;
+// below this, a hydrogen burning star is a red dwarf
+pub const RED_DWARF_LIMIT: f64 = 0.5;
+// no star much heavier than this has been found
+pub const MAX_MASS: f64 = 300.0;
+pub const AGE_OF_UNIVERSE: f64 = 13.8e9;
+
+// the most steps `simulate` will take
+pub const MAX_STEPS: usize = 10_000;
+
+// how long the sun burns hydrogen in its core, in years
+const SUN_MAIN_SEQUENCE: f64 = 1.0e10;
+// the red giant phase lasts about a tenth as long as the main sequence
+const GIANT_FRACTION: f64 = 0.1;
+
+// what is left after a star dies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Remnant {
+    WhiteDwarf,
+    NeutronStar,
+    BlackHole,
+}
+
+// one stage of a star's life, in years since the star formed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Phase {
+    pub stage: Star,
+    pub starts_at: f64,
+    // None for the last stage, which lasts forever
+    pub ends_at: Option<f64>,
+}
+
+// StellarEvolution : the life of a star worked out from its mass (in suns)
+// - heavier stars burn faster: the main sequence lasts 10 billion years * mass^-2.5
+// - red dwarfs slowly burn out into dead stars, heavier stars swell into red giants first
+#[derive(Debug, Clone)]
+pub struct StellarEvolution {
+    mass: f64,
+    phases: Vec<Phase>,
+}
+
+impl StellarEvolution {
+    pub fn new(mass: f64) -> Result<Self, MassError> {
+        if !mass.is_finite() || mass <= 0.0 || mass > MAX_MASS {
+            return Err(MassError(mass));
+        }
+
+        if mass < HYDROGEN_BURNING_LIMIT {
+            let phases = vec![Phase {
+                stage: Star::BrownDwarf,
+                starts_at: 0.0,
+                ends_at: None,
+            }];
+            return Ok(Self { mass, phases });
+        }
+
+        let main_sequence = main_sequence_lifetime(mass);
+        if mass < RED_DWARF_LIMIT {
+            let phases = vec![
+                Phase {
+                    stage: Star::RedDwarf,
+                    starts_at: 0.0,
+                    ends_at: Some(main_sequence),
+                },
+                Phase {
+                    stage: Star::DeadStar,
+                    starts_at: main_sequence,
+                    ends_at: None,
+                },
+            ];
+            return Ok(Self { mass, phases });
+        }
+
+        let giant_ends = main_sequence * (1.0 + GIANT_FRACTION);
+        let phases = vec![
+            Phase {
+                stage: Star::YellowStar,
+                starts_at: 0.0,
+                ends_at: Some(main_sequence),
+            },
+            Phase {
+                stage: Star::RedGiant,
+                starts_at: main_sequence,
+                ends_at: Some(giant_ends),
+            },
+            Phase {
+                stage: Star::DeadStar,
+                starts_at: giant_ends,
+                ends_at: None,
+            },
+        ];
+        Ok(Self { mass, phases })
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    pub fn stage_at(&self, age: f64) -> Star {
+        self.phases
+            .iter()
+            .rev()
+            .find(|phase| age >= phase.starts_at)
+            .unwrap_or(&self.phases[0])
+            .stage
+    }
+
+    // what the star turns into when it dies (brown dwarfs never die)
+    pub fn remnant(&self) -> Option<Remnant> {
+        match self.mass {
+            mass if mass < HYDROGEN_BURNING_LIMIT => None,
+            mass if mass < 8.0 => Some(Remnant::WhiteDwarf),
+            mass if mass < 20.0 => Some(Remnant::NeutronStar),
+            _ => Some(Remnant::BlackHole),
+        }
+    }
+
+    // the stage every `step` years, from 0 up to and including `until`
+    // - gives at most MAX_STEPS + 1 stages, so a tiny step can't eat all the memory
+    pub fn simulate(&self, step: f64, until: f64) -> Result<Vec<(f64, Star)>, SimulateError> {
+        if !step.is_finite() || step <= 0.0 {
+            return Err(SimulateError::Step(step));
+        }
+        if !until.is_finite() || until < 0.0 {
+            return Err(SimulateError::Until(until));
+        }
+        let steps = (until / step).floor();
+        if steps > MAX_STEPS as f64 {
+            return Err(SimulateError::TooManySteps { step, until });
+        }
+        let stages = (0..=steps as usize)
+            .map(|i| i as f64 * step)
+            .map(|age| (age, self.stage_at(age)))
+            .collect();
+        Ok(stages)
+    }
+
+    pub fn timeline(&self) -> String {
+        let mut report = format!("A star of {} suns:\n", self.mass);
+        for phase in &self.phases {
+            let line = match phase.ends_at {
+                Some(ends_at) => format!(
+                    "  {:?} from {} to {} ({})",
+                    phase.stage,
+                    format_years(phase.starts_at),
+                    format_years(ends_at),
+                    format_years(ends_at - phase.starts_at),
+                ),
+                None => format!(
+                    "  {:?} from {} on",
+                    phase.stage,
+                    format_years(phase.starts_at)
+                ),
+            };
+            report.push_str(&line);
+            if phase.starts_at > AGE_OF_UNIVERSE {
+                report.push_str(", later than the universe is old");
+            }
+            report.push('\n');
+        }
+        if let Some(remnant) = self.remnant() {
+            report.push_str(&format!("  and leaves a {remnant:?} behind\n"));
+        }
+        report
+    }
+}
+
+pub fn main_sequence_lifetime(mass: f64) -> f64 {
+    SUN_MAIN_SEQUENCE * mass.powf(-2.5)
+}
+
+// 4.6e9 -> "4.6 billion years"
+pub fn format_years(years: f64) -> String {
+    match years {
+        years if years >= 1e12 => format!("{:.1} trillion years", years / 1e12),
+        years if years >= 1e9 => format!("{:.1} billion years", years / 1e9),
+        years if years >= 1e6 => format!("{:.1} million years", years / 1e6),
+        years => format!("{years:.0} years"),
+    }
+}
+
+#[derive(Debug)]
+pub struct MassError(f64);
+
+impl fmt::Display for MassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a star can't have a mass of {} suns, expected more than 0 and up to {MAX_MASS}",
+            self.0
+        )
+    }
+}
+
+impl Error for MassError {}
+
+#[derive(Debug, PartialEq)]
+pub enum SimulateError {
+    Step(f64),
+    Until(f64),
+    TooManySteps { step: f64, until: f64 },
+}
+
+impl fmt::Display for SimulateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulateError::Step(step) => write!(
+                f,
+                "a step of {step} years can't be simulated, it has to be more than 0"
+            ),
+            SimulateError::Until(until) => write!(
+                f,
+                "can't simulate until {until} years, it has to be 0 or more"
+            ),
+            SimulateError::TooManySteps { step, until } => write!(
+                f,
+                "steps of {} up to {} are more than {MAX_STEPS} steps",
+                format_years(*step),
+                format_years(*until)
+            ),
+        }
+    }
+}
+
+impl Error for SimulateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sun() -> StellarEvolution {
+        StellarEvolution::new(1.0).unwrap()
+    }
+
+    #[test]
+    fn simulates_the_sun() {
+        let stages = sun().simulate(2.5e9, 1.5e10).unwrap();
+        let expected = [
+            (0.0, Star::YellowStar),
+            (2.5e9, Star::YellowStar),
+            (5.0e9, Star::YellowStar),
+            (7.5e9, Star::YellowStar),
+            (1.0e10, Star::RedGiant),
+            (1.25e10, Star::DeadStar),
+            (1.5e10, Star::DeadStar),
+        ];
+        assert_eq!(stages, expected);
+    }
+
+    #[test]
+    fn until_is_included_only_on_a_whole_step() {
+        assert_eq!(sun().simulate(1.0e9, 2.5e9).unwrap().len(), 3);
+        assert_eq!(
+            sun().simulate(1.0e9, 0.0).unwrap(),
+            [(0.0, Star::YellowStar)]
+        );
+        assert_eq!(sun().simulate(1.0e9, 1.0e8).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejects_bad_steps() {
+        for step in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(
+                matches!(sun().simulate(step, 1.0e10), Err(SimulateError::Step(_))),
+                "{step}"
+            );
+        }
+        for until in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(
+                matches!(sun().simulate(1.0e9, until), Err(SimulateError::Until(_))),
+                "{until}"
+            );
+        }
+        assert_eq!(
+            sun().simulate(0.0, 1.0).unwrap_err().to_string(),
+            "a step of 0 years can't be simulated, it has to be more than 0"
+        );
+    }
+
+    #[test]
+    fn caps_the_number_of_steps() {
+        let until = MAX_STEPS as f64 * 1.0e6;
+        assert_eq!(sun().simulate(1.0e6, until).unwrap().len(), MAX_STEPS + 1);
+        assert_eq!(
+            sun().simulate(1.0e6, until + 1.0e6),
+            Err(SimulateError::TooManySteps {
+                step: 1.0e6,
+                until: until + 1.0e6,
+            })
+        );
+        assert_eq!(
+            sun().simulate(1.0, 1.5e10).unwrap_err().to_string(),
+            "steps of 1 years up to 15.0 billion years are more than 10000 steps"
+        );
+        assert!(sun().simulate(f64::MIN_POSITIVE, 1.0).is_err());
+    }
+
+    #[test]
+    fn masses() {
+        assert!(StellarEvolution::new(0.0).is_err());
+        assert!(StellarEvolution::new(f64::NAN).is_err());
+        assert!(StellarEvolution::new(MAX_MASS + 1.0).is_err());
+        let brown_dwarf = StellarEvolution::new(0.05).unwrap();
+        assert_eq!(
+            brown_dwarf.simulate(1.0e12, 1.0e13).unwrap().last(),
+            Some(&(1.0e13, Star::BrownDwarf))
+        );
+        assert_eq!(brown_dwarf.remnant(), None);
+        assert_eq!(
+            StellarEvolution::new(25.0).unwrap().remnant(),
+            Some(Remnant::BlackHole)
+        );
+    }
+}
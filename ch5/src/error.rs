@@ -0,0 +1,246 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::validate::Violation;
+use crate::weather::WeatherError;
+
+// Error : every way a check in this project can fail
+// - validation failures keep what was expected and what was actually there,
+//   so callers can match on them instead of reading a message
+// - values are kept as the text they are shown as, so any number type fits
+// - errors that wrap another error give it back from `source()`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    NotEven {
+        actual: String,
+    },
+    Unexpected {
+        expected: String,
+        actual: String,
+    },
+    OutOfRange {
+        min: String,
+        max: String,
+        actual: String,
+    },
+    Parse {
+        input: String,
+        source: ParseIntError,
+    },
+    Weather(WeatherError),
+    // every rule a value broke, from validate::Rule
+    Violations(Vec<Violation>),
+}
+
+impl Error {
+    pub fn not_even(actual: impl fmt::Display) -> Self {
+        Error::NotEven {
+            actual: actual.to_string(),
+        }
+    }
+
+    pub fn unexpected(expected: impl fmt::Display, actual: impl fmt::Display) -> Self {
+        Error::Unexpected {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+
+    pub fn out_of_range(
+        min: impl fmt::Display,
+        max: impl fmt::Display,
+        actual: impl fmt::Display,
+    ) -> Self {
+        Error::OutOfRange {
+            min: min.to_string(),
+            max: max.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotEven { actual } => write!(f, "expected an even number, got {actual}"),
+            Error::Unexpected { expected, actual } => {
+                write!(f, "expected {expected}, got {actual}")
+            }
+            Error::OutOfRange { min, max, actual } => {
+                write!(f, "expected a value from {min} to {max}, got {actual}")
+            }
+            Error::Parse { input, .. } => write!(f, "could not read {input:?} as a number"),
+            Error::Weather(_) => write!(f, "bad weather data"),
+            Error::Violations(violations) => {
+                let messages = violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>();
+                let plural = if violations.len() == 1 { "" } else { "s" };
+                write!(
+                    f,
+                    "{} rule{plural} broken: {}",
+                    violations.len(),
+                    messages.join("; ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse { source, .. } => Some(source),
+            Error::Weather(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<WeatherError> for Error {
+    fn from(error: WeatherError) -> Self {
+        Error::Weather(error)
+    }
+}
+
+// "5" -> 5, keeping the text that didn't parse next to the reason
+pub fn parse_number(input: &str) -> Result<i32, Error> {
+    input.trim().parse().map_err(|source| Error::Parse {
+        input: input.to_string(),
+        source,
+    })
+}
+
+// the error and everything that caused it: "bad weather data: because ..."
+pub fn chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": because {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::Field;
+    use std::error::Error as _;
+
+    fn parse_error() -> Error {
+        parse_number("five").unwrap_err()
+    }
+
+    fn weather_error() -> Error {
+        Error::from(WeatherError::Missing(Field::City))
+    }
+
+    fn violations() -> Error {
+        Error::Violations(vec![
+            Violation {
+                field: Some("age".to_string()),
+                expected: "between 0 and 150".to_string(),
+                actual: "200".to_string(),
+            },
+            Violation {
+                field: None,
+                expected: "an even number".to_string(),
+                actual: "3".to_string(),
+            },
+        ])
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::not_even(7).to_string(),
+            "expected an even number, got 7"
+        );
+        assert_eq!(Error::unexpected(5, 4).to_string(), "expected 5, got 4");
+        assert_eq!(
+            Error::out_of_range(0, 100, 150).to_string(),
+            "expected a value from 0 to 100, got 150"
+        );
+        assert_eq!(
+            parse_error().to_string(),
+            "could not read \"five\" as a number"
+        );
+        assert_eq!(weather_error().to_string(), "bad weather data");
+        assert_eq!(
+            violations().to_string(),
+            "2 rules broken: age: expected between 0 and 150, got 200; expected an even number, got 3"
+        );
+        assert_eq!(
+            Error::Violations(Vec::new()).to_string(),
+            "0 rules broken: "
+        );
+    }
+
+    #[test]
+    fn values_are_kept_as_text() {
+        assert_eq!(
+            Error::not_even(i64::MAX),
+            Error::NotEven {
+                actual: i64::MAX.to_string()
+            }
+        );
+        assert_eq!(
+            Error::out_of_range(0.5, 1.5, -2),
+            Error::OutOfRange {
+                min: "0.5".to_string(),
+                max: "1.5".to_string(),
+                actual: "-2".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn only_wrapping_errors_have_a_source() {
+        let parse = parse_error();
+        let source = parse.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            "five".parse::<i32>().unwrap_err().to_string()
+        );
+
+        let weather = weather_error();
+        let source = weather.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            WeatherError::Missing(Field::City).to_string()
+        );
+
+        for error in [
+            Error::not_even(3),
+            Error::unexpected(5, 4),
+            Error::out_of_range(0, 1, 2),
+            violations(),
+        ] {
+            assert!(error.source().is_none(), "{error}");
+        }
+    }
+
+    #[test]
+    fn chain_follows_every_source() {
+        assert_eq!(
+            chain(&parse_error()),
+            "could not read \"five\" as a number: because invalid digit found in string"
+        );
+        assert_eq!(
+            chain(&weather_error()),
+            format!(
+                "bad weather data: because {}",
+                WeatherError::Missing(Field::City)
+            )
+        );
+        assert_eq!(chain(&Error::not_even(3)), "expected an even number, got 3");
+    }
+
+    #[test]
+    fn parse_number_trims() {
+        assert_eq!(parse_number(" 42 "), Ok(42));
+        assert!(matches!(parse_error(), Error::Parse { input, .. } if input == "five"));
+    }
+}
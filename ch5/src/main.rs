@@ -1,6 +1,14 @@
 use std::cmp::PartialOrd;
 use std::fmt::{Debug, Display};
 
+use error::Error;
+use validate::Rule;
+use weather::{DailySummary, TemperatureUnit, WeatherRecord};
+
+mod error;
+mod validate;
+mod weather;
+
 fn main() {
     // Generics : when to use more than one type
     //            maybe one type, maybe another type
@@ -54,7 +62,11 @@ fn main() {
     let small = vec![1, 2];
     let big = vec![1, 2, 3, 4, 5];
     // If you unwrap a value that is None, the program will panic
-    println!("{:?}, {:?}", try_take_fifth(small), try_take_fifth(big).unwrap());
+    println!(
+        "{:?}, {:?}",
+        try_take_fifth(small),
+        try_take_fifth(big).unwrap()
+    );
 
     // Option - with match to handle
     let small = vec![1, 2];
@@ -93,6 +105,69 @@ fn main() {
         println!("{:?}", check_if_five(number));
     }
 
+    // Error is an enum, so the caller can match on what went wrong
+    for number in [4, 5, 100] {
+        match see_if_number_is_even(number).and_then(|_| check_if_five(number)) {
+            Ok(number) => println!("{number} is fine"),
+            Err(Error::NotEven { actual }) => println!("{actual} is odd"),
+            Err(Error::Unexpected { expected, actual }) => {
+                println!("wanted {expected}, not {actual}")
+            }
+            Err(other) => println!("{other}"),
+        }
+    }
+    let percent = error::parse_number("150").and_then(|number| match number {
+        0..=100 => Ok(number),
+        _ => Err(Error::out_of_range(0, 100, number)),
+    });
+    println!("{}", percent.unwrap_err());
+    // source() gives the error underneath, so the whole chain can be printed
+    println!(
+        "{}",
+        error::chain(&error::parse_number("5 apples").unwrap_err())
+    );
+    let bad_line = "Berlin,cloudy,5,-7,178"
+        .parse::<WeatherRecord>()
+        .map_err(Error::from);
+    println!("{}", error::chain(&bad_line.unwrap_err()));
+
+    // the same checks as rules that can be put together, and tell you everything that's wrong
+    let age = Rule::range(0, 130)
+        .and(Rule::even())
+        .and(!Rule::one_of(vec![4, 13]))
+        .field("age");
+    println!("age must be {age}");
+    for value in [30, 13, 4, 151, -3] {
+        match age.validate(&value) {
+            Ok(()) => println!("{value} is fine"),
+            Err(Error::Violations(violations)) => {
+                for violation in &violations {
+                    println!("{value}: {violation} ({:?})", violation.field);
+                }
+            }
+            Err(other) => println!("{other}"),
+        }
+    }
+    let five = Rule::equals(5).or(Rule::odd().and(Rule::custom("a prime number", |&n: &i32| {
+        n > 1 && (2..n).all(|divisor| n % divisor != 0)
+    })));
+    println!("{five:?}");
+    for value in 4..=9 {
+        println!(
+            "{value}: {:?}",
+            five.validate(&value).map_err(|error| error.to_string())
+        );
+    }
+    let temperature =
+        Rule::range(-20.5, 40.0).and(Rule::custom("not freezing", |&t: &f64| t > 0.0));
+    println!(
+        "{:?} {}",
+        temperature.violations(&-30.0),
+        temperature.description()
+    );
+    let city = Rule::one_of(vec!["Berlin", "Athens"]).field("city");
+    println!("{}", city.validate(&"Tokyo").unwrap_err());
+
     // IF LET : do something if it matches, and don't do anything if it doesn't
     let my_vec = vec![2, 3, 4];
     // let get_one = my_vec.get(0);
@@ -121,19 +196,47 @@ fn main() {
         println!("The number is: {number}");
     }
 
-    // WHILE LET : like a `while` loop for `if let`
-    let weather_vec = vec![
-        vec!["Berlin", "cloudy", "5", "-7", "78"],
-        vec!["Athens", "sunny", "not humid", "20", "10", "50"],
-    ];
-    for mut city in weather_vec {
-        println!("For the city of {}:", city[0]);
-        while let Some(information) = city.pop() {
-            if let Ok(number) = information.parse::<i32>() {
-                println!("The number is: {number}");
-            }
-        }
+    // weather data with types: every field is checked and a bad one says what it expected
+    let weather_csv = "\
+city,condition,high,low,humidity
+Berlin,cloudy,5,-7,78
+Athens,sunny,not humid,20,10,50
+Athens,sunny,20,10,50%
+Oslo,snowy,28F,14F,85
+Cairo,sunny,35,21,
+Madrid,sunny,hot,21,30
+Lima,drizzle,19,16,80
+Tokyo,rainy,12,15,90
+Reykjavik,windy,3,-2,120
+Paris,partly cloudy,11°C,4°C,70";
+    let (records, errors) = weather::parse_csv(weather_csv);
+    // WHILE LET : like a `while` loop for `if let`, here taking records off the end until there are none
+    let mut remaining = records.clone();
+    while let Some(record) = remaining.pop() {
+        println!("{record}");
+        println!("  {}", record.display_in(TemperatureUnit::Fahrenheit));
+    }
+    for error in &errors {
+        println!("{error}");
+        println!("  {:?}", error.error);
+    }
+    if let Some(summary) = DailySummary::from_records(&records) {
+        println!("{summary}");
+        println!("{}", summary.display_in(TemperatureUnit::Fahrenheit));
     }
+    let berlin = "Berlin,cloudy,5,-7,78".parse::<WeatherRecord>();
+    println!(
+        "{:?}",
+        berlin.map(|berlin| (
+            berlin.high_in(TemperatureUnit::Fahrenheit),
+            berlin.low_in(TemperatureUnit::Fahrenheit)
+        ))
+    );
+    println!(
+        "{:.1} {:.1}",
+        weather::celsius_to_fahrenheit(100.0),
+        weather::fahrenheit_to_celsius(-40.0)
+    );
 }
 
 // people would say :
@@ -154,7 +257,10 @@ struct Animal {
 }
 
 fn compare_and_display<T: Display, U: Display + PartialOrd>(statement: T, input_1: U, input_2: U) {
-    println!("{statement}! Is {input_1} greater than {input_2}? {}", input_1 > input_2);
+    println!(
+        "{statement}! Is {input_1} greater than {input_2}? {}",
+        input_1 > input_2
+    );
 }
 
 fn compare_and_display_by_where<T, U>(statement: T, input_1: U, input_2: U)
@@ -162,7 +268,10 @@ where
     T: Display,
     U: Display + PartialOrd,
 {
-    println!("{statement}! Is {input_1} greater than {input_2}? {}", input_1 > input_2);
+    println!(
+        "{statement}! Is {input_1} greater than {input_2}? {}",
+        input_1 > input_2
+    );
 }
 
 fn try_take_fifth(value: Vec<i32>) -> Option<i32> {
@@ -186,17 +295,17 @@ fn check_error() -> Result<(), ()> {
     Ok(())
 }
 
-fn see_if_number_is_even(input: i32) -> Result<(), ()> {
+fn see_if_number_is_even(input: i32) -> Result<(), Error> {
     if input % 2 == 0 {
         Ok(())
     } else {
-        Err(())
+        Err(Error::not_even(input))
     }
 }
 
-fn check_if_five(number: i32) -> Result<i32, String> {
+fn check_if_five(number: i32) -> Result<i32, Error> {
     match number {
         5 => Ok(number),
-        _ => Err(format!("Sorry, bad number. Expected: 5 Got: {number}")),
+        _ => Err(Error::unexpected(5, number)),
     }
 }
@@ -0,0 +1,317 @@
+use std::fmt::{self, Display};
+use std::ops::{Not, Rem};
+
+use crate::error::Error;
+
+// one rule that a value didn't follow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    // the form field the value came from, if the rule was given one
+    pub field: Option<String>,
+    // what the value should have been, like "between 0 and 100"
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(field) = &self.field {
+            write!(f, "{field}: ")?;
+        }
+        write!(f, "expected {}, got {}", self.expected, self.actual)
+    }
+}
+
+type Check<T> = Box<dyn Fn(&T) -> Vec<Violation>>;
+
+// Rule : a check on a value that can be combined with other rules
+// - `and` keeps the violations of both sides, so a value hears about everything it got wrong
+// - `or` and `!rule` give one violation describing the whole rule
+pub struct Rule<T> {
+    description: String,
+    // "and" or "or" when the rule joins two others, so descriptions get parentheses where they need them
+    joined_by: Option<&'static str>,
+    check: Check<T>,
+}
+
+impl<T: PartialOrd + Display + 'static> Rule<T> {
+    // a rule from any test, `description` says what a passing value is
+    pub fn custom(description: &str, test: impl Fn(&T) -> bool + 'static) -> Self {
+        let expected = description.to_string();
+        Self {
+            description: description.to_string(),
+            joined_by: None,
+            check: Box::new(move |value| {
+                if test(value) {
+                    Vec::new()
+                } else {
+                    vec![Violation {
+                        field: None,
+                        expected: expected.clone(),
+                        actual: value.to_string(),
+                    }]
+                }
+            }),
+        }
+    }
+
+    // min and max are allowed too
+    pub fn range(min: T, max: T) -> Self {
+        let description = format!("between {min} and {max}");
+        Self::custom(&description, move |value| *value >= min && *value <= max)
+    }
+
+    pub fn equals(expected: T) -> Self {
+        let description = format!("equal to {expected}");
+        Self::custom(&description, move |value| *value == expected)
+    }
+
+    pub fn one_of(allowed: Vec<T>) -> Self {
+        let names = allowed
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        let description = format!("one of {}", names.join(", "));
+        Self::custom(&description, move |value| allowed.contains(value))
+    }
+
+    pub fn and(self, other: Rule<T>) -> Self {
+        let description = format!("{} and {}", self.grouped("and"), other.grouped("and"));
+        Self {
+            description,
+            joined_by: Some("and"),
+            check: Box::new(move |value| {
+                let mut violations = (self.check)(value);
+                violations.extend((other.check)(value));
+                violations
+            }),
+        }
+    }
+
+    pub fn or(self, other: Rule<T>) -> Self {
+        let description = format!("{} or {}", self.grouped("or"), other.grouped("or"));
+        let expected = description.clone();
+        Self {
+            description,
+            joined_by: Some("or"),
+            check: Box::new(move |value| {
+                if (self.check)(value).is_empty() || (other.check)(value).is_empty() {
+                    Vec::new()
+                } else {
+                    vec![Violation {
+                        field: None,
+                        expected: expected.clone(),
+                        actual: value.to_string(),
+                    }]
+                }
+            }),
+        }
+    }
+
+    // every violation of this rule says which field it is about
+    pub fn field(self, name: &str) -> Self {
+        let name = name.to_string();
+        Self {
+            description: self.description,
+            joined_by: self.joined_by,
+            check: Box::new(move |value| {
+                (self.check)(value)
+                    .into_iter()
+                    .map(|violation| Violation {
+                        field: Some(name.clone()),
+                        ..violation
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    // "(a or b) and c" instead of "a or b and c", but "a and b and c" stays as it is
+    fn grouped(&self, outer: &str) -> String {
+        match self.joined_by {
+            Some(inner) if inner != outer => format!("({})", self.description),
+            _ => self.description.clone(),
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    // every rule the value broke, not just the first one
+    pub fn violations(&self, value: &T) -> Vec<Violation> {
+        (self.check)(value)
+    }
+
+    pub fn validate(&self, value: &T) -> Result<(), Error> {
+        let violations = self.violations(value);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Violations(violations))
+        }
+    }
+}
+
+// for floats too: 4.0 is even, 3.0 is odd and 2.5 is neither
+impl<T: PartialOrd + Display + Rem<Output = T> + From<u8> + Clone + 'static> Rule<T> {
+    pub fn even() -> Self {
+        Self::custom("an even number", |value| {
+            value.clone() % T::from(2) == T::from(0)
+        })
+    }
+
+    // the remainder is 1 or -1, so it isn't 0 but is a whole number
+    pub fn odd() -> Self {
+        Self::custom("an odd number", |value| {
+            let remainder = value.clone() % T::from(2);
+            remainder != T::from(0) && remainder % T::from(1) == T::from(0)
+        })
+    }
+}
+
+// `!Rule::equals(13)` passes for everything but 13
+impl<T: PartialOrd + Display + 'static> Not for Rule<T> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        let description = format!("not {}", self.grouped("not"));
+        let expected = description.clone();
+        Self {
+            description,
+            joined_by: None,
+            check: Box::new(move |value| {
+                if (self.check)(value).is_empty() {
+                    vec![Violation {
+                        field: None,
+                        expected: expected.clone(),
+                        actual: value.to_string(),
+                    }]
+                } else {
+                    Vec::new()
+                }
+            }),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Rule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rule")
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for Rule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(violations: &[Violation]) -> Vec<&str> {
+        violations
+            .iter()
+            .map(|violation| violation.expected.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parity_of_negative_numbers() {
+        for n in [-4, -2, 0, 2] {
+            assert!(Rule::even().violations(&n).is_empty(), "{n}");
+            assert_eq!(Rule::odd().violations(&n).len(), 1, "{n}");
+        }
+        for n in [-3, -1, 1, 3] {
+            assert!(Rule::odd().violations(&n).is_empty(), "{n}");
+            assert_eq!(Rule::even().violations(&n).len(), 1, "{n}");
+        }
+    }
+
+    #[test]
+    fn non_integers_are_neither_even_nor_odd() {
+        for n in [2.5, -2.5, 0.5, -1.5] {
+            assert_eq!(Rule::even().violations(&n).len(), 1, "{n}");
+            assert_eq!(Rule::odd().violations(&n).len(), 1, "{n}");
+        }
+        assert!(Rule::even().violations(&-4.0).is_empty());
+        assert!(Rule::odd().violations(&-3.0).is_empty());
+        assert_eq!(expected(&Rule::odd().violations(&2.5)), ["an odd number"]);
+    }
+
+    #[test]
+    fn and_reports_both_sides() {
+        let rule = Rule::range(0, 10).and(Rule::even());
+        assert_eq!(
+            expected(&rule.violations(&11)),
+            ["between 0 and 10", "an even number"]
+        );
+        assert_eq!(expected(&rule.violations(&12)), ["between 0 and 10"]);
+        assert!(rule.validate(&4).is_ok());
+        assert!(
+            matches!(rule.validate(&-1), Err(Error::Violations(violations)) if violations.len() == 2)
+        );
+    }
+
+    #[test]
+    fn or_and_not_report_one_violation() {
+        let either = Rule::equals(1).or(Rule::equals(2));
+        assert_eq!(
+            expected(&either.violations(&3)),
+            ["equal to 1 or equal to 2"]
+        );
+        assert!(either.violations(&2).is_empty());
+
+        let neither = !Rule::one_of(vec![4, 13]).and(Rule::even());
+        let violations = neither.violations(&4);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].expected,
+            "not (one of 4, 13 and an even number)"
+        );
+        assert_eq!(violations[0].actual, "4");
+        assert!(neither.violations(&13).is_empty());
+    }
+
+    #[test]
+    fn field_tags_nested_violations() {
+        let rule = Rule::range(0, 130)
+            .and(Rule::even())
+            .field("age")
+            .and(Rule::equals(7));
+        let violations = rule.violations(&131);
+        let fields = violations
+            .iter()
+            .map(|violation| violation.field.as_deref())
+            .collect::<Vec<Option<&str>>>();
+        assert_eq!(fields, [Some("age"), Some("age"), None]);
+        assert_eq!(
+            violations[0].to_string(),
+            "age: expected between 0 and 130, got 131"
+        );
+    }
+
+    #[test]
+    fn descriptions_get_parentheses_where_they_need_them() {
+        let rule = Rule::equals(1).or(Rule::equals(2)).and(Rule::odd());
+        assert_eq!(
+            rule.description(),
+            "(equal to 1 or equal to 2) and an odd number"
+        );
+        let rule = Rule::equals(1).and(Rule::odd()).or(Rule::even());
+        assert_eq!(
+            rule.description(),
+            "(equal to 1 and an odd number) or an even number"
+        );
+        let rule = Rule::range(0, 9).and(Rule::odd()).and(Rule::equals(3));
+        assert_eq!(
+            rule.description(),
+            "between 0 and 9 and an odd number and equal to 3"
+        );
+        assert_eq!((!Rule::equals(13)).description(), "not equal to 13");
+    }
+}
@@ -0,0 +1,666 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// the coldest and hottest temperatures ever measured on Earth, with some room
+pub const MIN_CELSIUS: f64 = -95.0;
+pub const MAX_CELSIUS: f64 = 60.0;
+
+const FIELDS: [Field; 5] = [
+    Field::City,
+    Field::Condition,
+    Field::High,
+    Field::Low,
+    Field::Humidity,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Condition {
+    Sunny,
+    PartlyCloudy,
+    Cloudy,
+    Foggy,
+    Windy,
+    Rainy,
+    Snowy,
+    Stormy,
+}
+
+impl FromStr for Condition {
+    type Err = ();
+
+    // "sunny", "Partly cloudy", "partly-cloudy", "rain" and the like
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let name = input.trim().to_lowercase().replace(['-', '_'], " ");
+        match name.as_str() {
+            "sunny" | "sun" | "clear" => Ok(Condition::Sunny),
+            "partly cloudy" => Ok(Condition::PartlyCloudy),
+            "cloudy" | "overcast" => Ok(Condition::Cloudy),
+            "foggy" | "fog" => Ok(Condition::Foggy),
+            "windy" | "wind" => Ok(Condition::Windy),
+            "rainy" | "rain" => Ok(Condition::Rainy),
+            "snowy" | "snow" => Ok(Condition::Snowy),
+            "stormy" | "storm" | "thunderstorm" => Ok(Condition::Stormy),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Condition::Sunny => "sunny",
+            Condition::PartlyCloudy => "partly cloudy",
+            Condition::Cloudy => "cloudy",
+            Condition::Foggy => "foggy",
+            Condition::Windy => "windy",
+            Condition::Rainy => "rainy",
+            Condition::Snowy => "snowy",
+            Condition::Stormy => "stormy",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+}
+
+pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+pub fn fahrenheit_to_celsius(fahrenheit: f64) -> f64 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+// the columns of a weather line, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    City,
+    Condition,
+    High,
+    Low,
+    Humidity,
+}
+
+impl Field {
+    // what a valid value looks like, for error messages
+    fn expected(self) -> &'static str {
+        match self {
+            Field::City => "a city name",
+            Field::Condition => {
+                "one of sunny, partly cloudy, cloudy, foggy, windy, rainy, snowy, stormy"
+            }
+            Field::High | Field::Low => "a temperature like 5, -7.5, 5C or 41F",
+            Field::Humidity => "a percentage from 0 to 100, like 78 or 78%",
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Field::City => "city",
+            Field::Condition => "condition",
+            Field::High => "high",
+            Field::Low => "low",
+            Field::Humidity => "humidity",
+        };
+        f.write_str(name)
+    }
+}
+
+// WeatherRecord : one city's weather for a day
+// - a CSV line is city,condition,high,low,humidity like "Berlin,cloudy,5,-7,78"
+// - temperatures are kept in Celsius, a value ending in F is converted when it is read
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherRecord {
+    pub city: String,
+    pub condition: Condition,
+    pub high: f64,
+    pub low: f64,
+    pub humidity: u8,
+}
+
+impl WeatherRecord {
+    pub fn from_csv_line(line: &str) -> Result<Self, WeatherError> {
+        let fields = line.split(',').map(str::trim).collect::<Vec<&str>>();
+        let [city, condition, high, low, humidity] = fields[..] else {
+            return Err(WeatherError::FieldCount {
+                expected: FIELDS.len(),
+                found: fields.len(),
+            });
+        };
+        for (field, value) in FIELDS.into_iter().zip(&fields) {
+            if value.is_empty() {
+                return Err(WeatherError::Missing(field));
+            }
+        }
+
+        let invalid = |field: Field, value: &str| WeatherError::Invalid {
+            field,
+            value: value.to_string(),
+        };
+        let condition = condition
+            .parse()
+            .map_err(|_| invalid(Field::Condition, condition))?;
+        let high = parse_temperature(Field::High, high)?;
+        let low = parse_temperature(Field::Low, low)?;
+        if low > high {
+            return Err(WeatherError::LowAboveHigh { low, high });
+        }
+        let humidity_number = humidity.strip_suffix('%').unwrap_or(humidity).trim();
+        let humidity = match humidity_number.parse::<i64>() {
+            Ok(percent @ 0..=100) => percent as u8,
+            Ok(_) => {
+                return Err(WeatherError::OutOfRange {
+                    field: Field::Humidity,
+                    value: humidity.to_string(),
+                })
+            }
+            Err(_) => return Err(invalid(Field::Humidity, humidity)),
+        };
+
+        Ok(Self {
+            city: city.to_string(),
+            condition,
+            high,
+            low,
+            humidity,
+        })
+    }
+
+    pub fn high_in(&self, unit: TemperatureUnit) -> f64 {
+        in_unit(self.high, unit)
+    }
+
+    pub fn low_in(&self, unit: TemperatureUnit) -> f64 {
+        in_unit(self.low, unit)
+    }
+
+    pub fn display_in(&self, unit: TemperatureUnit) -> String {
+        format!(
+            "{}: {}, {:.1}{symbol} / {:.1}{symbol}, {}% humidity",
+            self.city,
+            self.condition,
+            self.high_in(unit),
+            self.low_in(unit),
+            self.humidity,
+            symbol = unit.symbol()
+        )
+    }
+}
+
+impl fmt::Display for WeatherRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_in(TemperatureUnit::Celsius))
+    }
+}
+
+impl FromStr for WeatherRecord {
+    type Err = WeatherError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_csv_line(input)
+    }
+}
+
+// reads every line it can and keeps the errors of the others with their line numbers,
+// so one bad line doesn't stop the rest
+// empty lines, '#' comments and a "city,..." header are skipped
+pub fn parse_csv(text: &str) -> (Vec<WeatherRecord>, Vec<LineError>) {
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        let is_header = index == 0 && trimmed.to_lowercase().starts_with("city,");
+        if trimmed.is_empty() || trimmed.starts_with('#') || is_header {
+            continue;
+        }
+        match WeatherRecord::from_csv_line(line) {
+            Ok(record) => records.push(record),
+            Err(error) => errors.push(LineError {
+                line: index + 1,
+                error,
+            }),
+        }
+    }
+    (records, errors)
+}
+
+// DailySummary : the weather of many cities on one day, taken together
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailySummary {
+    pub cities: usize,
+    pub average_high: f64,
+    pub average_low: f64,
+    pub average_humidity: f64,
+    // the city and its temperature
+    pub hottest: (String, f64),
+    pub coldest: (String, f64),
+    // how many cities had each condition, most common first
+    pub conditions: Vec<(Condition, usize)>,
+}
+
+impl DailySummary {
+    // None when there are no records
+    pub fn from_records(records: &[WeatherRecord]) -> Option<Self> {
+        let first = records.first()?;
+        let count = records.len() as f64;
+        let average =
+            |value: fn(&WeatherRecord) -> f64| records.iter().map(value).sum::<f64>() / count;
+
+        let mut hottest = first;
+        let mut coldest = first;
+        let mut conditions: Vec<(Condition, usize)> = Vec::new();
+        for record in records {
+            if record.high > hottest.high {
+                hottest = record;
+            }
+            if record.low < coldest.low {
+                coldest = record;
+            }
+            match conditions
+                .iter_mut()
+                .find(|(condition, _)| *condition == record.condition)
+            {
+                Some((_, seen)) => *seen += 1,
+                None => conditions.push((record.condition, 1)),
+            }
+        }
+        conditions.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        Some(Self {
+            cities: records.len(),
+            average_high: average(|record| record.high),
+            average_low: average(|record| record.low),
+            average_humidity: average(|record| f64::from(record.humidity)),
+            hottest: (hottest.city.clone(), hottest.high),
+            coldest: (coldest.city.clone(), coldest.low),
+            conditions,
+        })
+    }
+
+    pub fn display_in(&self, unit: TemperatureUnit) -> String {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|(condition, count)| format!("{count} {condition}"))
+            .collect::<Vec<String>>();
+        format!(
+            "{} cities: average {:.1}{symbol} / {:.1}{symbol}, {:.0}% humidity, hottest {} {:.1}{symbol}, coldest {} {:.1}{symbol}, {}",
+            self.cities,
+            in_unit(self.average_high, unit),
+            in_unit(self.average_low, unit),
+            self.average_humidity,
+            self.hottest.0,
+            in_unit(self.hottest.1, unit),
+            self.coldest.0,
+            in_unit(self.coldest.1, unit),
+            conditions.join(", "),
+            symbol = unit.symbol()
+        )
+    }
+}
+
+impl fmt::Display for DailySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_in(TemperatureUnit::Celsius))
+    }
+}
+
+fn in_unit(celsius: f64, unit: TemperatureUnit) -> f64 {
+    match unit {
+        TemperatureUnit::Celsius => celsius,
+        TemperatureUnit::Fahrenheit => celsius_to_fahrenheit(celsius),
+    }
+}
+
+// "5", "-7.5", "5C", "5 °C", "41F" -> Celsius
+fn parse_temperature(field: Field, value: &str) -> Result<f64, WeatherError> {
+    let lower = value.to_lowercase();
+    let (number, unit) = if let Some(number) = lower.strip_suffix('f') {
+        (number, TemperatureUnit::Fahrenheit)
+    } else {
+        (
+            lower.strip_suffix('c').unwrap_or(&lower),
+            TemperatureUnit::Celsius,
+        )
+    };
+    let number = number.trim().trim_end_matches('°').trim();
+
+    let invalid = || WeatherError::Invalid {
+        field,
+        value: value.to_string(),
+    };
+    let number = number.parse::<f64>().map_err(|_| invalid())?;
+    if !number.is_finite() {
+        return Err(invalid());
+    }
+    let celsius = match unit {
+        TemperatureUnit::Celsius => number,
+        TemperatureUnit::Fahrenheit => fahrenheit_to_celsius(number),
+    };
+    if !(MIN_CELSIUS..=MAX_CELSIUS).contains(&celsius) {
+        return Err(WeatherError::OutOfRange {
+            field,
+            value: value.to_string(),
+        });
+    }
+    Ok(celsius)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeatherError {
+    FieldCount { expected: usize, found: usize },
+    Missing(Field),
+    Invalid { field: Field, value: String },
+    OutOfRange { field: Field, value: String },
+    // in Celsius
+    LowAboveHigh { low: f64, high: f64 },
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeatherError::FieldCount { expected, found } => write!(
+                f,
+                "expected {expected} fields (city,condition,high,low,humidity) but found {found}"
+            ),
+            WeatherError::Missing(field) => write!(f, "{field} is empty, expected {}", field.expected()),
+            WeatherError::Invalid { field, value } => {
+                write!(f, "{field} {value:?} is not valid, expected {}", field.expected())
+            }
+            WeatherError::OutOfRange { field, value } => match field {
+                Field::Humidity => write!(f, "{field} {value:?} is out of range, expected 0 to 100"),
+                _ => write!(f, "{field} {value:?} is out of range, expected {MIN_CELSIUS}°C to {MAX_CELSIUS}°C"),
+            },
+            WeatherError::LowAboveHigh { low, high } => {
+                write!(f, "the low of {low:.1}°C is above the high of {high:.1}°C")
+            }
+        }
+    }
+}
+
+impl Error for WeatherError {}
+
+// a WeatherError and the line of the CSV it came from, counting from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    pub line: usize,
+    pub error: WeatherError,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl Error for LineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(line: &str) -> WeatherRecord {
+        line.parse().unwrap()
+    }
+
+    fn error(line: &str) -> WeatherError {
+        line.parse::<WeatherRecord>().unwrap_err()
+    }
+
+    #[test]
+    fn reads_a_line() {
+        assert_eq!(
+            record("Berlin, cloudy, 5, -7.5, 78"),
+            WeatherRecord {
+                city: "Berlin".to_string(),
+                condition: Condition::Cloudy,
+                high: 5.0,
+                low: -7.5,
+                humidity: 78,
+            }
+        );
+        assert_eq!(
+            record("Paris,Partly-cloudy,11,4,70").condition,
+            Condition::PartlyCloudy
+        );
+        assert_eq!(
+            record("Lima,thunderstorm,19,16,80").condition,
+            Condition::Stormy
+        );
+    }
+
+    #[test]
+    fn temperature_units() {
+        let oslo = record("Oslo,snowy,32F,14f,85");
+        assert_eq!(oslo.high, 0.0);
+        assert_eq!(oslo.low, -10.0);
+        assert_eq!(oslo.high_in(TemperatureUnit::Fahrenheit), 32.0);
+        for (high, expected) in [
+            ("11C", 11.0),
+            ("11 c", 11.0),
+            ("11°C", 11.0),
+            ("11 °C", 11.0),
+            ("11°", 11.0),
+            ("50°F", 10.0),
+        ] {
+            assert_eq!(
+                record(&format!("Paris,sunny,{high},4,70")).high,
+                expected,
+                "{high}"
+            );
+        }
+        assert_eq!(
+            oslo.display_in(TemperatureUnit::Fahrenheit),
+            "Oslo: snowy, 32.0°F / 14.0°F, 85% humidity"
+        );
+        assert_eq!(
+            oslo.to_string(),
+            "Oslo: snowy, 0.0°C / -10.0°C, 85% humidity"
+        );
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(fahrenheit_to_celsius(-40.0), -40.0);
+    }
+
+    #[test]
+    fn humidity_can_have_a_percent_sign() {
+        assert_eq!(record("Athens,sunny,20,10,50%").humidity, 50);
+        assert_eq!(record("Athens,sunny,20,10,50 %").humidity, 50);
+        assert_eq!(record("Athens,sunny,20,10,0").humidity, 0);
+        assert_eq!(record("Athens,sunny,20,10,100%").humidity, 100);
+    }
+
+    #[test]
+    fn every_error() {
+        assert_eq!(
+            error("Berlin,cloudy,5,-7"),
+            WeatherError::FieldCount {
+                expected: 5,
+                found: 4
+            }
+        );
+        assert_eq!(
+            error("Athens,sunny,not humid,20,10,50"),
+            WeatherError::FieldCount {
+                expected: 5,
+                found: 6
+            }
+        );
+        assert_eq!(
+            error("Cairo,sunny,35,21,"),
+            WeatherError::Missing(Field::Humidity)
+        );
+        assert_eq!(error(",sunny,35,21,10"), WeatherError::Missing(Field::City));
+        assert_eq!(
+            error("Lima,drizzle,19,16,80"),
+            WeatherError::Invalid {
+                field: Field::Condition,
+                value: "drizzle".to_string()
+            }
+        );
+        assert_eq!(
+            error("Madrid,sunny,hot,21,30"),
+            WeatherError::Invalid {
+                field: Field::High,
+                value: "hot".to_string()
+            }
+        );
+        assert_eq!(
+            error("Madrid,sunny,30,NaN,30"),
+            WeatherError::Invalid {
+                field: Field::Low,
+                value: "NaN".to_string()
+            }
+        );
+        assert_eq!(
+            error("Madrid,sunny,30,20,wet"),
+            WeatherError::Invalid {
+                field: Field::Humidity,
+                value: "wet".to_string()
+            }
+        );
+        assert_eq!(
+            error("Reykjavik,windy,3,-2,120"),
+            WeatherError::OutOfRange {
+                field: Field::Humidity,
+                value: "120".to_string()
+            }
+        );
+        assert_eq!(
+            error("Vostok,snowy,-20,-100,10"),
+            WeatherError::OutOfRange {
+                field: Field::Low,
+                value: "-100".to_string()
+            }
+        );
+        assert_eq!(
+            error("Death Valley,sunny,150F,30,10"),
+            WeatherError::OutOfRange {
+                field: Field::High,
+                value: "150F".to_string()
+            }
+        );
+        assert_eq!(
+            error("Tokyo,rainy,12,15,90"),
+            WeatherError::LowAboveHigh {
+                low: 15.0,
+                high: 12.0
+            }
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            error("Tokyo,rainy,12,15,90").to_string(),
+            "the low of 15.0°C is above the high of 12.0°C"
+        );
+        assert_eq!(
+            error("Reykjavik,windy,3,-2,120").to_string(),
+            "humidity \"120\" is out of range, expected 0 to 100"
+        );
+        assert_eq!(
+            error("Cairo,sunny,35,21,").to_string(),
+            "humidity is empty, expected a percentage from 0 to 100, like 78 or 78%"
+        );
+    }
+
+    #[test]
+    fn parse_csv_skips_headers_and_comments_and_counts_lines() {
+        let text = "City,Condition,High,Low,Humidity\n# measured at noon\nBerlin,cloudy,5,-7,78\n\nTokyo,rainy,12,15,90\n  # another comment\nOslo,snowy,28F,14F,85\ncity,sunny,1,0,1";
+        let (records, errors) = parse_csv(text);
+        let cities = records
+            .iter()
+            .map(|record| record.city.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(cities, ["Berlin", "Oslo", "city"]);
+        assert_eq!(
+            errors,
+            [LineError {
+                line: 5,
+                error: WeatherError::LowAboveHigh {
+                    low: 15.0,
+                    high: 12.0
+                },
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "line 5: the low of 15.0°C is above the high of 12.0°C"
+        );
+        assert!(Error::source(&errors[0]).is_some());
+        assert_eq!(parse_csv(""), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn daily_summary() {
+        let records = [
+            record("Berlin,cloudy,5,-7,78"),
+            record("Athens,sunny,20,10,50"),
+            record("Cairo,sunny,35,21,20"),
+            record("London,rainy,11,5,90"),
+            record("Paris,cloudy,11,4,70"),
+            record("Rome,sunny,18,9,60"),
+        ];
+        let summary = DailySummary::from_records(&records).unwrap();
+        assert_eq!(summary.cities, 6);
+        assert_eq!(summary.average_high, 100.0 / 6.0);
+        assert_eq!(summary.average_low, 42.0 / 6.0);
+        assert_eq!(summary.average_humidity, 368.0 / 6.0);
+        assert_eq!(summary.hottest, ("Cairo".to_string(), 35.0));
+        assert_eq!(summary.coldest, ("Berlin".to_string(), -7.0));
+        // most common first, then in the order of `Condition` on a tie
+        assert_eq!(
+            summary.conditions,
+            [
+                (Condition::Sunny, 3),
+                (Condition::Cloudy, 2),
+                (Condition::Rainy, 1)
+            ]
+        );
+        assert!(DailySummary::from_records(&[]).is_none());
+    }
+
+    #[test]
+    fn summary_keeps_the_first_city_on_a_tie() {
+        let records = [
+            record("Oslo,windy,5,-2,80"),
+            record("Bergen,rainy,5,-2,90"),
+            record("Bern,foggy,4,-1,70"),
+        ];
+        let summary = DailySummary::from_records(&records).unwrap();
+        assert_eq!(summary.hottest.0, "Oslo");
+        assert_eq!(summary.coldest.0, "Oslo");
+        assert_eq!(
+            summary.conditions,
+            [
+                (Condition::Foggy, 1),
+                (Condition::Windy, 1),
+                (Condition::Rainy, 1)
+            ]
+        );
+        assert_eq!(
+            summary.to_string(),
+            "3 cities: average 4.7°C / -1.7°C, 80% humidity, hottest Oslo 5.0°C, coldest Oslo -2.0°C, 1 foggy, 1 windy, 1 rainy"
+        );
+    }
+}
@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::Path;
+
+use crate::suggest::DidYouMean;
+
+// a city we know about, together with the other names people use for it
+#[derive(Debug, Clone)]
+pub struct Place {
+    pub name: String,
+    pub country: String,
+    pub population: u32,
+    pub aliases: Vec<String>,
+}
+
+// Gazetteer : cities imported from CSV files exported from a spreadsheet
+// - the CSV needs a header row with `city`, `country` and `population` columns (in any order)
+// - an optional `aliases` column holds other names separated by `;` (e.g. "Reval" for Tallinn)
+// - lookups ignore case and accents, so "zurich" finds "Zürich"
+// - a miss suggests names within `max_distance` edits of the query
+#[derive(Debug)]
+pub struct Gazetteer {
+    places: Vec<Place>,
+    // normalized name or alias -> index into `places`
+    index: HashMap<String, usize>,
+    max_distance: usize,
+}
+
+impl Default for Gazetteer {
+    fn default() -> Self {
+        Self {
+            places: Vec::new(),
+            index: HashMap::new(),
+            max_distance: 2,
+        }
+    }
+}
+
+impl Gazetteer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // how many typos a suggestion may be away from the query
+    pub fn set_max_distance(&mut self, max_distance: usize) {
+        self.max_distance = max_distance;
+    }
+
+    pub fn from_csv_file<P: AsRef<Path>>(path: P) -> Result<Self, ImportError> {
+        let mut gazetteer = Self::new();
+        gazetteer.import_csv_file(path)?;
+        Ok(gazetteer)
+    }
+
+    pub fn from_csv_str(text: &str) -> Result<Self, ImportError> {
+        let mut gazetteer = Self::new();
+        gazetteer.import_csv_str(text)?;
+        Ok(gazetteer)
+    }
+
+    // returns how many cities were added
+    pub fn import_csv_file<P: AsRef<Path>>(&mut self, path: P) -> Result<usize, ImportError> {
+        let text = fs::read_to_string(path)?;
+        self.import_csv_str(&text)
+    }
+
+    // every row is checked before anything is added,
+    // so a bad file never leaves the gazetteer half imported
+    pub fn import_csv_str(&mut self, text: &str) -> Result<usize, ImportError> {
+        let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let Some((header_line, header)) = lines.next() else {
+            return Err(ImportError::MissingColumn("city"));
+        };
+        let columns = Columns::from_header(header_line, header)?;
+
+        let mut new_places = Vec::new();
+        for (line, row) in lines {
+            let fields =
+                split_csv_line(row).map_err(|message| ImportError::Row { line, message })?;
+            new_places.push((line, columns.place(line, &fields)?));
+        }
+
+        let mut seen = HashMap::new();
+        for (line, place) in &new_places {
+            for name in place.all_names() {
+                let key = normalize(name);
+                if self.index.contains_key(&key) || seen.insert(key, *line).is_some() {
+                    return Err(ImportError::DuplicateName {
+                        line: Some(*line),
+                        name: name.to_string(),
+                    });
+                }
+            }
+        }
+
+        let count = new_places.len();
+        for (_, place) in new_places {
+            self.add(place);
+        }
+        Ok(count)
+    }
+
+    // the same checks as an import: no name or alias may be taken already,
+    // and a place can't list its own name as an alias
+    pub fn insert(&mut self, place: Place) -> Result<(), ImportError> {
+        let mut seen = HashSet::new();
+        if let Some(name) = place.all_names().find(|name| {
+            let key = normalize(name);
+            self.index.contains_key(&key) || !seen.insert(key)
+        }) {
+            return Err(ImportError::DuplicateName {
+                line: None,
+                name: name.to_string(),
+            });
+        }
+        self.add(place);
+        Ok(())
+    }
+
+    // finds a city by its name or any of its aliases
+    pub fn lookup(&self, name: &str) -> Result<&Place, NotFound> {
+        let key = normalize(name);
+        match self.index.get(&key) {
+            Some(&i) => Ok(&self.places[i]),
+            None => Err(NotFound {
+                query: name.to_string(),
+                suggestions: self.suggestions(&key),
+            }),
+        }
+    }
+
+    pub fn cities_in(&self, country: &str) -> Vec<&Place> {
+        let country = normalize(country);
+        self.places
+            .iter()
+            .filter(|place| normalize(&place.country) == country)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.places.len()
+    }
+
+    fn add(&mut self, place: Place) {
+        let i = self.places.len();
+        for name in place.all_names() {
+            self.index.insert(normalize(name), i);
+        }
+        self.places.push(place);
+    }
+
+    // the closest names and aliases, reported as the city's main name
+    fn suggestions(&self, key: &str) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        for near in self.index.did_you_mean(key, self.max_distance) {
+            let name = &self.places[self.index[near]].name;
+            if !suggestions.contains(name) {
+                suggestions.push(name.clone());
+            }
+        }
+        suggestions
+    }
+}
+
+impl Place {
+    fn all_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(|alias| alias.as_str()))
+    }
+}
+
+// where each field lives in a row, found from the header
+struct Columns {
+    city: usize,
+    country: usize,
+    population: usize,
+    aliases: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(line: usize, header: &str) -> Result<Self, ImportError> {
+        let names = split_csv_line(header).map_err(|message| ImportError::Row { line, message })?;
+        let find = |column: &str| names.iter().position(|name| normalize(name) == column);
+
+        Ok(Self {
+            city: find("city").ok_or(ImportError::MissingColumn("city"))?,
+            country: find("country").ok_or(ImportError::MissingColumn("country"))?,
+            population: find("population").ok_or(ImportError::MissingColumn("population"))?,
+            aliases: find("aliases"),
+        })
+    }
+
+    fn place(&self, line: usize, fields: &[String]) -> Result<Place, ImportError> {
+        let field = |i: usize, column: &str| {
+            fields
+                .get(i)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| ImportError::Row {
+                    line,
+                    message: format!("missing value for `{column}`"),
+                })
+        };
+
+        let population = field(self.population, "population")?;
+        // spreadsheets like to export "1,306,784"
+        let digits = population
+            .chars()
+            .filter(|c| !matches!(c, ',' | '_' | ' '))
+            .collect::<String>();
+        let population = digits
+            .parse::<u32>()
+            .map_err(|source| ImportError::Population {
+                line,
+                value: population.to_string(),
+                source,
+            })?;
+
+        let aliases = match self.aliases.and_then(|i| fields.get(i)) {
+            Some(aliases) => aliases
+                .split(';')
+                .map(|alias| alias.trim())
+                .filter(|alias| !alias.is_empty())
+                .map(|alias| alias.to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(Place {
+            name: field(self.city, "city")?.to_string(),
+            country: field(self.country, "country")?.to_string(),
+            population,
+            aliases,
+        })
+    }
+}
+
+// splits one CSV line, handling "quoted, fields" and "" inside quotes
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unclosed quote".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+// lowercase, trim and strip accents so "  ZÜRICH" and "zurich" are the same key
+// - accents written as a separate combining mark, like "u\u{308}", are dropped too
+pub fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if is_combining_mark(c) {
+            continue;
+        }
+        match fold_accent(c) {
+            Some(folded) => normalized.push_str(folded),
+            None => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+// the Combining Diacritical Marks block
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+fn fold_accent(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ț' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    MissingColumn(&'static str),
+    Row {
+        line: usize,
+        message: String,
+    },
+    Population {
+        line: usize,
+        value: String,
+        source: ParseIntError,
+    },
+    // `line` is None when the place was inserted by hand instead of imported
+    DuplicateName {
+        line: Option<usize>,
+        name: String,
+    },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "could not read the file: {e}"),
+            ImportError::MissingColumn(column) => write!(f, "the header has no `{column}` column"),
+            ImportError::Row { line, message } => write!(f, "line {line}: {message}"),
+            ImportError::Population { line, value, .. } => {
+                write!(f, "line {line}: `{value}` is not a population")
+            }
+            ImportError::DuplicateName { line: None, name } => {
+                write!(f, "{name} is already in the gazetteer")
+            }
+            ImportError::DuplicateName {
+                line: Some(line),
+                name,
+            } => {
+                write!(f, "line {line}: {name} is already in the gazetteer")
+            }
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(e) => Some(e),
+            ImportError::Population { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+// returned by `lookup` when no city or alias matches
+#[derive(Debug)]
+pub struct NotFound {
+    pub query: String,
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no city called {:?}", self.query)?;
+        if !self.suggestions.is_empty() {
+            write!(f, ", did you mean {}?", self.suggestions.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for NotFound {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "city,country,population,aliases\nZürich,Switzerland,421878,Zurigo\nTallinn,Estonia,437619,Reval\n";
+
+    fn place(name: &str, aliases: &[&str]) -> Place {
+        Place {
+            name: name.to_string(),
+            country: "Nowhere".to_string(),
+            population: 1,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn combining_accents_match_precomposed_ones() {
+        let gazetteer = Gazetteer::from_csv_str(CSV).unwrap();
+        for query in ["Zu\u{308}rich", "ZU\u{308}RICH", "zurich", "  Zürich "] {
+            assert_eq!(
+                gazetteer
+                    .lookup(query)
+                    .map(|place| place.name.as_str())
+                    .ok(),
+                Some("Zürich"),
+                "{query}"
+            );
+        }
+        assert_eq!(normalize("Zu\u{308}rich"), normalize("Zürich"));
+        assert_eq!(normalize("Sa\u{303}o Paulo"), "sao paulo");
+    }
+
+    #[test]
+    fn insert_rejects_an_alias_of_its_own_name() {
+        let mut gazetteer = Gazetteer::from_csv_str(CSV).unwrap();
+        let error = gazetteer.insert(place("Gimli", &["GIMLI"])).unwrap_err();
+        assert!(
+            matches!(error, ImportError::DuplicateName { line: None, ref name } if name == "GIMLI")
+        );
+        assert!(gazetteer.lookup("Gimli").is_err());
+        assert_eq!(gazetteer.len(), 2);
+    }
+
+    #[test]
+    fn insert_and_import_agree() {
+        let import =
+            Gazetteer::from_csv_str("city,country,population,aliases\nGimli,Canada,6000,gimli\n");
+        assert!(matches!(
+            import,
+            Err(ImportError::DuplicateName { line: Some(2), .. })
+        ));
+
+        let mut gazetteer = Gazetteer::from_csv_str(CSV).unwrap();
+        assert!(gazetteer.insert(place("Revał", &[])).is_err());
+        assert!(gazetteer.insert(place("Riga", &[])).is_ok());
+        assert!(matches!(
+            gazetteer.insert(place("Nowhere", &["Reval"])),
+            Err(ImportError::DuplicateName { .. })
+        ));
+        assert!(gazetteer.insert(place("Gimli", &["Gimli Beach"])).is_ok());
+        assert_eq!(gazetteer.lookup("gimli beach").unwrap().name, "Gimli");
+    }
+
+    #[test]
+    fn import_errors() {
+        let error = Gazetteer::from_csv_str("city,population\nGimli,2246\n").unwrap_err();
+        assert!(matches!(error, ImportError::MissingColumn("country")));
+        assert_eq!(error.to_string(), "the header has no `country` column");
+        assert!(matches!(
+            Gazetteer::from_csv_str("\n\n"),
+            Err(ImportError::MissingColumn("city"))
+        ));
+
+        let error = Gazetteer::from_csv_str(
+            "city,country,population\nGimli,Canada,2246\nTartu,Estonia,lots\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&error, ImportError::Population { line: 3, value, .. } if value == "lots")
+        );
+        assert_eq!(error.to_string(), "line 3: `lots` is not a population");
+        assert!(error.source().is_some());
+        assert!(matches!(
+            Gazetteer::from_csv_str("city,country,population\nGimli,Canada,-5\n"),
+            Err(ImportError::Population { line: 2, .. })
+        ));
+
+        let error = Gazetteer::from_csv_str("city,country,population\nGimli,,2246\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: missing value for `country`");
+        let error =
+            Gazetteer::from_csv_str("city,country,population\n\"Gimli,Canada,2246\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: unclosed quote");
+
+        let error =
+            Gazetteer::from_csv_str("city,country,population\nGimli,Canada,1\ngimli,Canada,2\n")
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: gimli is already in the gazetteer"
+        );
+    }
+
+    #[test]
+    fn quoted_fields_keep_their_commas() {
+        let csv = "population,city,country,aliases\n\"1,306,784\",Calgary,Canada,\"Cowtown; \"\"YYC\"\"\"\n2246,\"Gimli, Manitoba\",Canada,\n";
+        let gazetteer = Gazetteer::from_csv_str(csv).unwrap();
+        let calgary = gazetteer.lookup("cowtown").unwrap();
+        assert_eq!(calgary.population, 1_306_784);
+        assert_eq!(calgary.aliases, ["Cowtown", "\"YYC\""]);
+        let gimli = gazetteer.lookup("gimli, manitoba").unwrap();
+        assert_eq!((gimli.country.as_str(), gimli.population), ("Canada", 2246));
+    }
+
+    #[test]
+    fn a_bad_row_imports_nothing() {
+        let mut gazetteer = Gazetteer::from_csv_str(CSV).unwrap();
+        let error = gazetteer
+            .import_csv_str("city,country,population\nRiga,Latvia,605273\nReval,Estonia,1\n");
+        assert!(matches!(
+            error,
+            Err(ImportError::DuplicateName { line: Some(3), .. })
+        ));
+        assert_eq!(gazetteer.len(), 2);
+        assert!(gazetteer.lookup("Riga").is_err());
+    }
+}
@@ -1,6 +1,12 @@
-use std::collections::{BinaryHeap, BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque};
 use std::num::ParseIntError;
 
+use gazetteer::Gazetteer;
+use suggest::DidYouMean;
+
+mod gazetteer;
+mod suggest;
+
 fn main() {
     // HashMap is a collection made out of keys and values
     // The keys of a HashMap are not ordered
@@ -42,7 +48,6 @@ fn main() {
     // using [vec].get(i).expect("message") is better than using [vec].get(i).unwrap()
     // expect("message") provides more information
 
-
     // HashMap
     let mut tallinn = City {
         name: "Tallinn".to_string(),
@@ -86,6 +91,11 @@ fn main() {
     println!("{:?}", city_hashmap["Bielefeld"]);
     println!("{:?}", city_hashmap.get("Bielefeld"));
     println!("{:?}", city_hashmap.get("Bielefeldd"));
+    // any map with string keys can suggest the closest keys instead
+    println!(
+        "Did you mean {:?}?",
+        city_hashmap.did_you_mean("Bielefeldd", 2)
+    );
 
     // insert same key
     let mut book_hashmap = HashMap::new();
@@ -167,9 +177,11 @@ fn main() {
     }
 
     // BTreeMap
-    let many_numbers = vec![37, 3, 25, 11, 27, 3, 37, 21, 36, 19, 37, 30, 48,
-                            28, 16, 33, 2, 10, 1, 12, 38, 35, 30, 21, 20, 38, 16, 48, 39, 31, 41,
-                            32, 50, 7, 15, 1, 20, 3, 33, 12, 1, 11, 34, 38, 49, 1, 27, 9, 46, 33];
+    let many_numbers = vec![
+        37, 3, 25, 11, 27, 3, 37, 21, 36, 19, 37, 30, 48, 28, 16, 33, 2, 10, 1, 12, 38, 35, 30, 21,
+        20, 38, 16, 48, 39, 31, 41, 32, 50, 7, 15, 1, 20, 3, 33, 12, 1, 11, 34, 38, 49, 1, 27, 9,
+        46, 33,
+    ];
 
     let mut number_set = BTreeSet::new();
     for number in many_numbers {
@@ -238,6 +250,56 @@ fn main() {
     let my_vec = vec![8, 9, 10];
     let fourth = my_vec.get(3).unwrap_or(&0);
     println!("{fourth}");
+
+    // Gazetteer : cities loaded from a CSV file instead of hardcoded vecs
+    // lookup() gives a Result, so a missing city never panics like city_hashmap["..."]
+    let csv_path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/cities.csv");
+    match Gazetteer::from_csv_file(csv_path) {
+        Ok(mut gazetteer) => {
+            let helsinki = gazetteer::Place {
+                name: "Helsinki".to_string(),
+                country: "Finland".to_string(),
+                population: 674_963,
+                aliases: vec!["Helsingfors".to_string()],
+            };
+            if let Err(e) = gazetteer.insert(helsinki) {
+                println!("{e}");
+            }
+            println!("Loaded {} cities", gazetteer.len());
+            gazetteer.set_max_distance(3);
+            for query in [
+                "Bielefeld",
+                "ZURICH",
+                "Reval",
+                "krakau",
+                "helsingfors",
+                "Bielefeldd",
+                "Talin",
+                "Dorpad",
+            ] {
+                match gazetteer.lookup(query) {
+                    Ok(place) => println!(
+                        "{query} is {} in {} (population {})",
+                        place.name, place.country, place.population
+                    ),
+                    Err(e) => println!("{e}"),
+                }
+            }
+            let canadian_cities = gazetteer
+                .cities_in("canada")
+                .iter()
+                .map(|place| place.name.as_str())
+                .collect::<Vec<&str>>();
+            println!("Canadian cities: {canadian_cities:?}");
+        }
+        Err(e) => println!("Could not load {csv_path}: {e}"),
+    }
+
+    // a bad row is reported with its line number
+    let bad_csv = "city,country,population\nGimli,Canada,lots";
+    if let Err(e) = Gazetteer::from_csv_str(bad_csv) {
+        println!("{e}");
+    }
 }
 
 struct City {
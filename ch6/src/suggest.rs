@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, HashMap};
+
+// "did you mean" suggestions for anything keyed by strings
+// - keys are compared by edit distance (Levenshtein), ignoring case
+// - only keys within `max_distance` edits are returned, closest first
+pub trait DidYouMean {
+    fn did_you_mean(&self, query: &str, max_distance: usize) -> Vec<&str>;
+}
+
+impl<K: AsRef<str>, V> DidYouMean for HashMap<K, V> {
+    fn did_you_mean(&self, query: &str, max_distance: usize) -> Vec<&str> {
+        nearest(self.keys().map(|key| key.as_ref()), query, max_distance)
+    }
+}
+
+impl<K: AsRef<str>, V> DidYouMean for BTreeMap<K, V> {
+    fn did_you_mean(&self, query: &str, max_distance: usize) -> Vec<&str> {
+        nearest(self.keys().map(|key| key.as_ref()), query, max_distance)
+    }
+}
+
+// the closest keys to `query`, sorted by distance and then alphabetically
+pub fn nearest<'a, I>(keys: I, query: &str, max_distance: usize) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let query = query.to_lowercase();
+    let mut found = keys
+        .into_iter()
+        .filter_map(|key| {
+            let distance = edit_distance(&key.to_lowercase(), &query);
+            (distance <= max_distance).then_some((distance, key))
+        })
+        .collect::<Vec<(usize, &str)>>();
+    found.sort();
+    found.dedup();
+    found.into_iter().map(|(_, key)| key).collect()
+}
+
+// how many single char inserts, deletes or substitutions turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    // only the previous row of the table is needed
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gazetteer::Gazetteer;
+
+    #[test]
+    fn edit_distance_counts_chars() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        // one char each, however many bytes it takes
+        assert_eq!(edit_distance("zürich", "zurich"), 1);
+        assert_eq!(edit_distance("kraków", "krakow"), 1);
+        assert_eq!(edit_distance("서울", "서울시"), 1);
+        assert_eq!(edit_distance("🦀", "🦞"), 1);
+        assert_eq!(edit_distance("東京", "京東"), 2);
+    }
+
+    #[test]
+    fn nearest_cuts_off_at_max_distance() {
+        let keys = ["Tartu", "Tallinn", "Turku", "Tampere"];
+        assert_eq!(nearest(keys, "tartu", 0), ["Tartu"]);
+        assert_eq!(nearest(keys, "TARTU", 0), ["Tartu"]);
+        assert_eq!(nearest(keys, "Tartuu", 1), ["Tartu"]);
+        assert_eq!(nearest(keys, "Tarku", 1), ["Tartu", "Turku"]);
+        assert!(nearest(keys, "Helsinki", 3).is_empty());
+        assert!(nearest([], "Tartu", 10).is_empty());
+    }
+
+    #[test]
+    fn nearest_sorts_by_distance_then_alphabetically() {
+        let keys = ["bat", "cat", "act", "hat", "cart"];
+        assert_eq!(nearest(keys, "cat", 1), ["cat", "bat", "cart", "hat"]);
+        // the same key twice is only suggested once
+        assert_eq!(
+            nearest(["Oslo", "Oslo", "Olso"], "oslo", 2),
+            ["Oslo", "Olso"]
+        );
+    }
+
+    #[test]
+    fn nearest_with_empty_and_multibyte_queries() {
+        assert_eq!(nearest(["", "a", "ab"], "", 1), ["", "a"]);
+        assert_eq!(nearest(["Zürich", "Zurich"], "zurich", 0), ["Zurich"]);
+        // closest first, even when the tie-break would put it last
+        assert_eq!(
+            nearest(["Zürich", "Zurich"], "ZÜRICH", 1),
+            ["Zürich", "Zurich"]
+        );
+        assert_eq!(nearest(["서울", "부산"], "서을", 1), ["서울"]);
+    }
+
+    #[test]
+    fn maps_suggest_their_keys() {
+        let hashmap = HashMap::from([("Bielefeld", 334_002), ("Karlsruhe", 308_436)]);
+        assert_eq!(hashmap.did_you_mean("Bielefeldd", 2), ["Bielefeld"]);
+        let btreemap = BTreeMap::from([("Tartu".to_string(), ()), ("Turku".to_string(), ())]);
+        assert_eq!(btreemap.did_you_mean("Torku", 1), ["Turku"]);
+        assert_eq!(btreemap.did_you_mean("Tarku", 1), ["Tartu", "Turku"]);
+    }
+
+    #[test]
+    fn the_gazetteer_suggests_main_names() {
+        let mut gazetteer =
+            Gazetteer::from_csv_file(concat!(env!("CARGO_MANIFEST_DIR"), "/data/cities.csv"))
+                .unwrap();
+        let miss = gazetteer.lookup("Karlsruh").unwrap_err();
+        assert_eq!(miss.suggestions, ["Karlsruhe"]);
+        assert_eq!(
+            miss.to_string(),
+            "no city called \"Karlsruh\", did you mean Karlsruhe?"
+        );
+        // an alias is suggested as the city it belongs to
+        assert_eq!(
+            gazetteer.lookup("Krakow City").unwrap_err().suggestions,
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            gazetteer.lookup("Crakow").unwrap_err().suggestions,
+            ["Kraków"]
+        );
+        assert_eq!(
+            gazetteer.lookup("Tortu").unwrap_err().suggestions,
+            ["Tartu"]
+        );
+        // both Tartu and its alias Dorpat are close, it's still suggested once
+        assert_eq!(gazetteer.lookup("Tarpat").unwrap_err().suggestions.len(), 1);
+
+        gazetteer.set_max_distance(0);
+        assert!(gazetteer
+            .lookup("Karlsruh")
+            .unwrap_err()
+            .suggestions
+            .is_empty());
+        assert_eq!(
+            gazetteer.lookup("Atlantis").unwrap_err().to_string(),
+            "no city called \"Atlantis\""
+        );
+    }
+}
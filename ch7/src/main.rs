@@ -25,7 +25,6 @@ fn main() {
     // both `String` and `str` implement AsRef<str>
     // taking an AsRef<str> is a convenient way to take both a `String` and a `&str` in a function
 
-
     // let rover = Dog {
     //     name: "Rover".to_string(),
     // };
@@ -52,8 +51,10 @@ fn main() {
     println!("{mr_mantle}");
 
     print_excitedly(mr_mantle.to_string());
-    println!("Mr. Mantle's String is {} letters long.",
-             mr_mantle.to_string().chars().count());
+    println!(
+        "Mr. Mantle's String is {} letters long.",
+        mr_mantle.to_string().chars().count()
+    );
 
     // example1 : our own struct
     let radagast = Wizard { health: 60 };
@@ -78,10 +79,32 @@ fn main() {
     let turku = City::new("Turku", 186_756);
 
     let finland_cities = vec![helsinki, turku];
-    let finland = Country::from(finland_cities);
+    let mut finland = Country::from(finland_cities);
 
     finland.print_cities();
 
+    // example 4 : aggregates on Country
+    for city in [City::new("Tampere", 244_029), City::new("turku", 186_756)] {
+        if let Err(e) = finland.add_city(city) {
+            println!("Could not add the city: {e}");
+        }
+    }
+    if let Some(removed) = finland.remove_city("Tampere") {
+        println!("Removed {}", removed.name);
+    }
+    println!("Total population: {}", finland.total_population());
+    if let (Some(largest), Some(smallest)) = (finland.largest_city(), finland.smallest_city()) {
+        println!(
+            "Largest city: {}, smallest city: {}",
+            largest.name, smallest.name
+        );
+    }
+    println!("Median city size: {:?}", finland.median_city_size());
+    println!(
+        "People per km²: {:?}",
+        finland.population_density(338_455.0)
+    );
+
     // newtype idiom
     let my_file = File(String::from("I am file contents"));
     let my_string = String::from("I am file contents");
@@ -249,21 +272,96 @@ struct Country {
     cities: Vec<City>,
 }
 
+// goes through add_city, so a name that comes up twice keeps only the first city
 impl From<Vec<City>> for Country {
     fn from(cities: Vec<City>) -> Self {
-        Self { cities }
+        let mut country = Self {
+            cities: Vec::with_capacity(cities.len()),
+        };
+        for city in cities {
+            let _ = country.add_city(city);
+        }
+        country
     }
 }
 
 impl Country {
     fn print_cities(&self) {
         for city in &self.cities {
-            println!(
-                "{:?} has a population of {:?}.",
-                city.name, city.population,
-            )
+            println!("{:?} has a population of {:?}.", city.name, city.population,)
         }
     }
+
+    // u64 so that adding up many big cities can't overflow
+    fn total_population(&self) -> u64 {
+        self.cities
+            .iter()
+            .map(|city| u64::from(city.population))
+            .sum()
+    }
+
+    fn largest_city(&self) -> Option<&City> {
+        self.cities.iter().max_by_key(|city| city.population)
+    }
+
+    fn smallest_city(&self) -> Option<&City> {
+        self.cities.iter().min_by_key(|city| city.population)
+    }
+
+    // with an even number of cities, the median is the average of the middle two
+    fn median_city_size(&self) -> Option<f64> {
+        let mut populations = self
+            .cities
+            .iter()
+            .map(|city| city.population)
+            .collect::<Vec<u32>>();
+        populations.sort();
+
+        let middle = populations.len() / 2;
+        match populations.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(f64::from(populations[middle])),
+            _ => Some((f64::from(populations[middle - 1]) + f64::from(populations[middle])) / 2.0),
+        }
+    }
+
+    // people per square kilometre, counting only the cities we know about
+    fn population_density(&self, area_km2: f64) -> Option<f64> {
+        if area_km2 > 0.0 {
+            Some(self.total_population() as f64 / area_km2)
+        } else {
+            None
+        }
+    }
+
+    // city names are compared without caring about upper or lower case
+    fn add_city(&mut self, city: City) -> Result<(), DuplicateCity> {
+        if self.find_city(&city.name).is_some() {
+            return Err(DuplicateCity(city.name));
+        }
+        self.cities.push(city);
+        Ok(())
+    }
+
+    fn remove_city(&mut self, name: &str) -> Option<City> {
+        let index = self.find_city(name)?;
+        Some(self.cities.remove(index))
+    }
+
+    fn find_city(&self, name: &str) -> Option<usize> {
+        self.cities
+            .iter()
+            .position(|city| city.name.to_lowercase() == name.to_lowercase())
+    }
+}
+
+#[derive(Debug)]
+struct DuplicateCity(String);
+
+impl fmt::Display for DuplicateCity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "the country already has a city called {}", self.0)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -280,3 +378,104 @@ impl std::fmt::Display for File {
 fn print_it<T: AsRef<str>>(input: T) {
     println!("{}", input.as_ref());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn country(cities: &[(&str, u32)]) -> Country {
+        Country::from(
+            cities
+                .iter()
+                .map(|&(name, population)| City::new(name, population))
+                .collect::<Vec<City>>(),
+        )
+    }
+
+    fn names(country: &Country) -> Vec<&str> {
+        country
+            .cities
+            .iter()
+            .map(|city| city.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn from_drops_duplicate_names() {
+        let finland = country(&[("Helsinki", 631_695), ("Turku", 186_756), ("HELSINKI", 1)]);
+        assert_eq!(names(&finland), ["Helsinki", "Turku"]);
+        assert_eq!(finland.total_population(), 818_451);
+    }
+
+    #[test]
+    fn aggregates() {
+        let finland = country(&[
+            ("Helsinki", 631_695),
+            ("Turku", 186_756),
+            ("Tampere", 244_029),
+        ]);
+        assert_eq!(finland.total_population(), 1_062_480);
+        assert_eq!(finland.largest_city().unwrap().name, "Helsinki");
+        assert_eq!(finland.smallest_city().unwrap().name, "Turku");
+
+        // adds up past u32::MAX without overflowing
+        let big = country(&[("A", u32::MAX), ("B", u32::MAX)]);
+        assert_eq!(big.total_population(), 2 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn median_city_size() {
+        assert_eq!(country(&[]).median_city_size(), None);
+        assert_eq!(country(&[("A", 5)]).median_city_size(), Some(5.0));
+        assert_eq!(
+            country(&[("A", 9), ("B", 1), ("C", 4)]).median_city_size(),
+            Some(4.0)
+        );
+        assert_eq!(
+            country(&[("A", 9), ("B", 1), ("C", 4), ("D", 7)]).median_city_size(),
+            Some(5.5)
+        );
+        assert_eq!(
+            country(&[("A", u32::MAX), ("B", u32::MAX)]).median_city_size(),
+            Some(f64::from(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn empty_country() {
+        let empty = country(&[]);
+        assert_eq!(empty.total_population(), 0);
+        assert!(empty.largest_city().is_none());
+        assert!(empty.smallest_city().is_none());
+        assert_eq!(empty.population_density(100.0), Some(0.0));
+    }
+
+    #[test]
+    fn population_density() {
+        let finland = country(&[("Helsinki", 600_000), ("Turku", 200_000)]);
+        assert_eq!(finland.population_density(400.0), Some(2_000.0));
+        assert_eq!(finland.population_density(0.0), None);
+        assert_eq!(finland.population_density(-1.0), None);
+        assert_eq!(finland.population_density(f64::NAN), None);
+    }
+
+    #[test]
+    fn add_find_and_remove() {
+        let mut finland = country(&[("Helsinki", 631_695)]);
+        assert!(finland.add_city(City::new("Turku", 186_756)).is_ok());
+
+        let error = finland.add_city(City::new("turku", 1)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the country already has a city called turku"
+        );
+        assert_eq!(names(&finland), ["Helsinki", "Turku"]);
+
+        assert_eq!(finland.find_city("TURKU"), Some(1));
+        assert_eq!(finland.find_city("Tampere"), None);
+
+        assert_eq!(finland.remove_city("helsinki").unwrap().name, "Helsinki");
+        assert_eq!(finland.remove_city("Helsinki").map(|city| city.name), None);
+        assert_eq!(names(&finland), ["Turku"]);
+    }
+}